@@ -14,11 +14,15 @@ use sc_finality_grandpa::{
 	FinalityProofProvider as GrandpaFinalityProofProvider, StorageAndProofProvider, SharedVoterState,
 };
 
+/// The RPC extensions served by a full node.
+pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
+
 // Our native executor instance.
 native_executor_instance!(
 	pub Executor,
 	node_template_runtime::api::dispatch,
 	node_template_runtime::native_version,
+	frame_benchmarking::benchmarking::HostFunctions,
 );
 
 /// Starts a `ServiceBuilder` for a full service.
@@ -78,6 +82,14 @@ macro_rules! new_full_start {
 				import_setup = Some((grandpa_block_import, grandpa_link));
 
 				Ok(import_queue)
+			})?
+			.with_rpc_extensions(|builder| -> Result<crate::service::RpcExtension, _> {
+				use pallet_poe_rpc::{Poe, PoeApi};
+
+				let mut io = jsonrpc_core::IoHandler::default();
+				io.extend_with(PoeApi::to_delegate(Poe::new(builder.client().clone())));
+
+				Ok(io)
 			})?;
 
 		(builder, import_setup, inherent_data_providers)
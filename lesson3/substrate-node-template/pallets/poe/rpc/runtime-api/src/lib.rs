@@ -0,0 +1,26 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_core::H256;
+use sp_std::prelude::*;
+
+pub use pallet_poe::ClaimInfo;
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber, Moment> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Moment: Codec,
+	{
+		/// The owner and metadata of `claim`, if it exists.
+		fn claim_of(claim: Vec<u8>) -> Option<ClaimInfo<AccountId, BlockNumber, Moment>>;
+
+		/// One page of the claims owned by `account`. Pages start from 0.
+		fn claims_of(account: AccountId, page: u32) -> Vec<Vec<u8>>;
+
+		/// Whether `leaf` is committed to by the anchored Merkle root `root`.
+		fn verify_inclusion(root: H256, leaf: Vec<u8>, proof: Vec<H256>) -> bool;
+	}
+}
@@ -0,0 +1,113 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, H256};
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_poe_rpc_runtime_api::{ClaimInfo, PoeApi as PoeRuntimeApi};
+
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber, Moment> {
+	/// The owner and metadata of `claim`, if it exists.
+	#[rpc(name = "poe_claimOf")]
+	fn claim_of(
+		&self,
+		claim: Bytes,
+		at: Option<BlockHash>,
+	) -> Result<Option<ClaimInfo<AccountId, BlockNumber, Moment>>>;
+
+	/// One page of the claims owned by `account`. Pages start from 0.
+	#[rpc(name = "poe_claimsOf")]
+	fn claims_of(&self, account: AccountId, page: u32, at: Option<BlockHash>) -> Result<Vec<Bytes>>;
+
+	/// Whether `leaf` is committed to by the anchored Merkle root `root`.
+	#[rpc(name = "poe_verifyInclusion")]
+	fn verify_inclusion(
+		&self,
+		root: H256,
+		leaf: Bytes,
+		proof: Vec<H256>,
+		at: Option<BlockHash>,
+	) -> Result<bool>;
+}
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i64 = 1;
+
+fn runtime_error(message: &str, e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(RUNTIME_ERROR),
+		message: message.into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+/// An implementation of the proof of existence RPC API.
+pub struct Poe<C, B> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B> Poe<C, B> {
+	/// Create a new `Poe` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Poe { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId, BlockNumber, Moment> PoeApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Moment>
+	for Poe<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, Moment>,
+	AccountId: Codec,
+	BlockNumber: Codec,
+	Moment: Codec,
+{
+	fn claim_of(
+		&self,
+		claim: Bytes,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<ClaimInfo<AccountId, BlockNumber, Moment>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claim_of(&at, claim.to_vec())
+			.map_err(|e| runtime_error("Unable to query claim.", e))
+	}
+
+	fn claims_of(
+		&self,
+		account: AccountId,
+		page: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.claims_of(&at, account, page)
+			.map(|claims| claims.into_iter().map(Bytes).collect())
+			.map_err(|e| runtime_error("Unable to query claims of account.", e))
+	}
+
+	fn verify_inclusion(
+		&self,
+		root: H256,
+		leaf: Bytes,
+		proof: Vec<H256>,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<bool> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.verify_inclusion(&at, root, leaf.to_vec(), proof)
+			.map_err(|e| runtime_error("Unable to verify inclusion.", e))
+	}
+}
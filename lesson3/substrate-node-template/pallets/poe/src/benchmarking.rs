@@ -0,0 +1,260 @@
+//! Benchmarks for the proof of existence pallet.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+
+use frame_benchmarking::{benchmarks, account};
+use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+
+const SEED: u32 = 0;
+
+// Half the maximum balance, so that the account can still be paid for a claim it sells.
+fn funded_account<T: Trait>(name: &'static str) -> T::AccountId {
+	let who: T::AccountId = account(name, 0, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / BalanceOf::<T>::from(2u32));
+	who
+}
+
+fn approve_operator_of<T: Trait>(owner: &T::AccountId, operator: &T::AccountId) -> dispatch::DispatchResult {
+	Module::<T>::approve_operator(RawOrigin::Signed(owner.clone()).into(), T::Lookup::unlookup(operator.clone()))
+}
+
+fn signers<T: Trait>(s: u32) -> Vec<T::AccountId> {
+	(0..s).map(|i| account("signer", i, SEED)).collect()
+}
+
+fn create_claim_of<T: Trait>(owner: &T::AccountId, claim: &[u8]) -> dispatch::DispatchResult {
+	Module::<T>::create_claim(
+		RawOrigin::Signed(owner.clone()).into(),
+		claim.to_vec(),
+		Vec::new(),
+		HashAlgorithm::default(),
+		Some(system::Module::<T>::block_number() + 10u32.into()),
+	)
+}
+
+// Creates `claim` for `owner` with an expiry, followed by another claim, so that removing
+// `claim` from the owner index has to swap the other one into its slot.
+fn create_worst_case_claim<T: Trait>(owner: &T::AccountId, claim: &[u8]) -> dispatch::DispatchResult {
+	create_claim_of::<T>(owner, claim)?;
+	create_claim_of::<T>(owner, b"other")
+}
+
+benchmarks! {
+	_ { }
+
+	create_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded_account::<T>("caller");
+		let claim = vec![0u8; l as usize];
+		let expires_at = system::Module::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), Vec::new(), HashAlgorithm::default(), Some(expires_at))
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, caller);
+	}
+
+	create_claim_from_content {
+		let c in 1 .. T::MaxContentLength::get();
+		let caller = funded_account::<T>("caller");
+		let content = vec![0u8; c as usize];
+		let claim = T::ContentHashing::hash(&content).as_ref().to_vec();
+	}: _(RawOrigin::Signed(caller.clone()), content)
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, caller);
+	}
+
+	create_claim_for {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded_account::<T>("caller");
+		let claim = vec![0u8; l as usize];
+		let payload = Module::<T>::create_claim_for_payload(&claim, 0);
+		let (owner, signature) = T::BenchmarkSigner::sign(&payload);
+	}: _(RawOrigin::Signed(caller), owner.clone(), claim.clone(), signature, 0)
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, owner);
+	}
+
+	revoke_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded_account::<T>("caller");
+		let claim = vec![0u8; l as usize];
+		create_worst_case_claim::<T>(&caller, &claim)?;
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(!Proofs::<T>::contains_key(&claim));
+	}
+
+	transfer_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let caller = funded_account::<T>("caller");
+		let recipient = funded_account::<T>("recipient");
+		let claim = vec![0u8; l as usize];
+		create_worst_case_claim::<T>(&caller, &claim)?;
+	}: _(RawOrigin::Signed(caller), claim.clone(), T::Lookup::unlookup(recipient.clone()))
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, recipient);
+	}
+
+	offer_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let owner = funded_account::<T>("owner");
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let recipient: T::AccountId = account("recipient", 0, SEED);
+		let claim = vec![0u8; l as usize];
+		create_claim_of::<T>(&owner, &claim)?;
+		approve_operator_of::<T>(&owner, &caller)?;
+	}: _(RawOrigin::Signed(caller), claim.clone(), T::Lookup::unlookup(recipient.clone()))
+	verify {
+		assert_eq!(PendingTransfers::<T>::get(&claim), Some(recipient));
+	}
+
+	accept_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let owner = funded_account::<T>("owner");
+		let caller = funded_account::<T>("caller");
+		let claim = vec![0u8; l as usize];
+		create_worst_case_claim::<T>(&owner, &claim)?;
+		Module::<T>::offer_claim(
+			RawOrigin::Signed(owner).into(),
+			claim.clone(),
+			T::Lookup::unlookup(caller.clone()),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, caller);
+	}
+
+	cancel_claim_offer {
+		let l in 1 .. T::MaxClaimLength::get();
+		let owner = funded_account::<T>("owner");
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let claim = vec![0u8; l as usize];
+		create_claim_of::<T>(&owner, &claim)?;
+		Module::<T>::offer_claim(
+			RawOrigin::Signed(owner).into(),
+			claim.clone(),
+			T::Lookup::unlookup(caller.clone()),
+		)?;
+	}: _(RawOrigin::Signed(caller), claim.clone())
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(&claim));
+	}
+
+	set_claim_price {
+		let l in 1 .. T::MaxClaimLength::get();
+		let owner = funded_account::<T>("owner");
+		let caller: T::AccountId = account("caller", 0, SEED);
+		let claim = vec![0u8; l as usize];
+		create_claim_of::<T>(&owner, &claim)?;
+		approve_operator_of::<T>(&owner, &caller)?;
+		let price: BalanceOf<T> = 100u32.into();
+	}: _(RawOrigin::Signed(caller), claim.clone(), Some(price))
+	verify {
+		assert_eq!(ClaimPrices::<T>::get(&claim), Some(price));
+	}
+
+	buy_claim {
+		let l in 1 .. T::MaxClaimLength::get();
+		let owner = funded_account::<T>("owner");
+		let caller = funded_account::<T>("caller");
+		let claim = vec![0u8; l as usize];
+		create_worst_case_claim::<T>(&owner, &claim)?;
+		let price: BalanceOf<T> = 100u32.into();
+		Module::<T>::set_claim_price(RawOrigin::Signed(owner).into(), claim.clone(), Some(price))?;
+	}: _(RawOrigin::Signed(caller.clone()), claim.clone(), price)
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, caller);
+	}
+
+	open_cosigned_claim {
+		let s in 1 .. T::MaxSigners::get();
+		let caller = funded_account::<T>("caller");
+		let claim = vec![0u8; T::MaxClaimLength::get() as usize];
+	}: _(RawOrigin::Signed(caller), claim.clone(), signers::<T>(s), s)
+	verify {
+		assert!(CosignedClaims::<T>::contains_key(&claim));
+	}
+
+	// The last endorsement meets the threshold and records the claim.
+	endorse_claim {
+		let s in 1 .. T::MaxSigners::get();
+		let creator = funded_account::<T>("creator");
+		let claim = vec![0u8; T::MaxClaimLength::get() as usize];
+		let signers = signers::<T>(s);
+		Module::<T>::open_cosigned_claim(RawOrigin::Signed(creator.clone()).into(), claim.clone(), signers.clone(), s)?;
+		for signer in &signers[1..] {
+			Module::<T>::endorse_claim(RawOrigin::Signed(signer.clone()).into(), claim.clone())?;
+		}
+	}: _(RawOrigin::Signed(signers[0].clone()), claim.clone())
+	verify {
+		assert_eq!(Proofs::<T>::get(&claim).owner, creator);
+	}
+
+	anchor_root {
+		let caller = funded_account::<T>("caller");
+		let root = H256::repeat_byte(1);
+	}: _(RawOrigin::Signed(caller), root, 1)
+	verify {
+		assert!(AnchoredRoots::<T>::contains_key(&root));
+	}
+
+	remove_root {
+		let caller = funded_account::<T>("caller");
+		let root = H256::repeat_byte(1);
+		Module::<T>::anchor_root(RawOrigin::Signed(caller.clone()).into(), root, 1)?;
+	}: _(RawOrigin::Signed(caller), root)
+	verify {
+		assert!(!AnchoredRoots::<T>::contains_key(&root));
+	}
+
+	approve_operator {
+		let caller = funded_account::<T>("caller");
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(operator.clone()))
+	verify {
+		assert!(Module::<T>::is_operator(&caller, &operator));
+	}
+
+	revoke_operator {
+		let caller = funded_account::<T>("caller");
+		let operator: T::AccountId = account("operator", 0, SEED);
+		Module::<T>::approve_operator(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Lookup::unlookup(operator.clone()),
+		)?;
+	}: _(RawOrigin::Signed(caller.clone()), T::Lookup::unlookup(operator.clone()))
+	verify {
+		assert!(!Module::<T>::is_operator(&caller, &operator));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock::{new_test_ext, Test};
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_benchmarks() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(test_benchmark_create_claim::<Test>());
+			assert_ok!(test_benchmark_create_claim_from_content::<Test>());
+			assert_ok!(test_benchmark_create_claim_for::<Test>());
+			assert_ok!(test_benchmark_revoke_claim::<Test>());
+			assert_ok!(test_benchmark_transfer_claim::<Test>());
+			assert_ok!(test_benchmark_offer_claim::<Test>());
+			assert_ok!(test_benchmark_accept_claim::<Test>());
+			assert_ok!(test_benchmark_cancel_claim_offer::<Test>());
+			assert_ok!(test_benchmark_set_claim_price::<Test>());
+			assert_ok!(test_benchmark_buy_claim::<Test>());
+			assert_ok!(test_benchmark_open_cosigned_claim::<Test>());
+			assert_ok!(test_benchmark_endorse_claim::<Test>());
+			assert_ok!(test_benchmark_anchor_root::<Test>());
+			assert_ok!(test_benchmark_remove_root::<Test>());
+			assert_ok!(test_benchmark_approve_operator::<Test>());
+			assert_ok!(test_benchmark_revoke_operator::<Test>());
+		});
+	}
+}
@@ -2,13 +2,21 @@
 
 /// A FRAME pallet proof of existence with necessary imports
 
+use codec::{Encode, Decode};
+#[cfg(feature = "std")]
+use serde::{Serialize, Deserialize};
 use frame_support::{
-	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure,
-	traits::{Get},
+	decl_module, decl_storage, decl_event, decl_error, dispatch, ensure, Parameter,
+	storage::IterableStorageMap,
+	traits::{Get, Currency, ReservableCurrency, ExistenceRequirement, BalanceStatus},
+	weights::Weight,
 };
-use frame_system::{self as system, ensure_signed};
-use sp_std::prelude::*;
-use sp_runtime::traits::StaticLookup;
+use frame_system::{self as system, ensure_signed, ensure_root};
+use pallet_timestamp as timestamp;
+use sp_core::H256;
+use sp_io::hashing::{blake2_256, keccak_256, sha2_256, twox_128};
+use sp_std::{prelude::*, collections::btree_map::BTreeMap};
+use sp_runtime::{RuntimeDebug, traits::{StaticLookup, Saturating, Zero, Verify, IdentifyAccount, Hash as HashT}};
 
 #[cfg(test)]
 mod mock;
@@ -16,8 +24,179 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod proptests;
+
+mod benchmarking;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+/// Number of claims returned per page by `Module::claims_of`.
+pub const CLAIMS_PER_PAGE: u32 = 20;
+
+/// Prefix of a hashed leaf in the Merkle trees behind anchored roots.
+const MERKLE_LEAF_PREFIX: &[u8] = &[0];
+/// Prefix of a hashed inner node in the Merkle trees behind anchored roots.
+const MERKLE_NODE_PREFIX: &[u8] = &[1];
+
+/// Domain separator of the payload signed for `create_claim_for`.
+const CREATE_CLAIM_FOR_CONTEXT: &[u8] = b"poe:create_claim_for";
+
+/// The storage prefix this pallet used before it got its own.
+const LEGACY_PREFIX: &[u8] = b"TemplateModule";
+
+/// The storage prefix of this pallet.
+const PREFIX: &[u8] = b"Poe";
+
+/// Storage items that existed under `LEGACY_PREFIX`.
+const LEGACY_ITEMS: &[&[u8]] = &[
+	b"Proofs",
+	b"OwnedClaims",
+	b"OwnedClaimsCount",
+	b"OwnedClaimsIndex",
+	b"ClaimPrices",
+	b"ClaimDeposits",
+	b"PendingTransfers",
+	b"ClaimExpiries",
+	b"Expiries",
+	b"AnchoredRoots",
+	b"StorageVersion",
+];
+
+/// The hash algorithm that produced a claim's bytes.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HashAlgorithm {
+	Blake2_256,
+	Sha2_256,
+	Keccak256,
+}
+
+impl Default for HashAlgorithm {
+	fn default() -> Self {
+		HashAlgorithm::Blake2_256
+	}
+}
+
+impl HashAlgorithm {
+	/// Hashes `data` with this algorithm.
+	pub fn hash(&self, data: &[u8]) -> [u8; 32] {
+		match self {
+			HashAlgorithm::Blake2_256 => blake2_256(data),
+			HashAlgorithm::Sha2_256 => sha2_256(data),
+			HashAlgorithm::Keccak256 => keccak_256(data),
+		}
+	}
+}
+
+/// Signs payloads with a key of its own choosing, for benchmarks that need a valid signature.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkSigner<AccountId, Signature> {
+	/// Signs `payload`, returning the signing account and the signature.
+	fn sign(payload: &[u8]) -> (AccountId, Signature);
+}
+
+/// The owner of a claim and the metadata recorded alongside it.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct ClaimInfo<AccountId, BlockNumber, Moment> {
+	/// The current owner of the claim.
+	pub owner: AccountId,
+	/// The block at which the claim was created or last changed hands.
+	pub block_number: BlockNumber,
+	/// The timestamp at which the claim was created.
+	pub created_at: Moment,
+	/// A free-text note, at most `MaxNoteLength` bytes.
+	pub note: Vec<u8>,
+	/// The hash algorithm that produced the claim.
+	pub hash_algorithm: HashAlgorithm,
+}
+
+/// A Merkle root anchored on chain, standing in for `leaf_count` claims.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct RootInfo<AccountId, Balance, BlockNumber> {
+	/// The account that anchored the root.
+	pub owner: AccountId,
+	/// The number of leaves committed to by the root.
+	pub leaf_count: u32,
+	/// The deposit reserved from the owner until the root is removed.
+	pub deposit: Balance,
+	/// The block at which the root was anchored.
+	pub block_number: BlockNumber,
+}
+
+/// A claim that must be endorsed by a set of signers before it is finalized.
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug)]
+pub struct CosignedClaim<AccountId, Balance> {
+	/// The account that opened the claim, and owns it once finalized.
+	pub creator: AccountId,
+	/// The accounts allowed to endorse the claim.
+	pub signers: Vec<AccountId>,
+	/// The number of endorsements needed to finalize the claim.
+	pub threshold: u32,
+	/// The signers that have endorsed the claim so far.
+	pub endorsements: Vec<AccountId>,
+	/// The deposit reserved from the creator when the claim was opened.
+	pub deposit: Balance,
+	/// Whether the threshold has been met and the claim recorded in `Proofs`.
+	pub finalized: bool,
+}
+
+/// What happened to a claim in a provenance record.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum ProvenanceAction {
+	Created,
+	Transferred,
+	Revoked,
+	Expired,
+}
+
+/// An entry in the provenance history of a claim.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ProvenanceRecord<AccountId, BlockNumber> {
+	/// What happened to the claim.
+	pub action: ProvenanceAction,
+	/// The account that caused it. None for root and for the chain itself, such as on expiry.
+	pub actor: Option<AccountId>,
+	/// The owner of the claim after the action, or the last owner if it was removed.
+	pub owner: AccountId,
+	/// The block at which it happened.
+	pub block_number: BlockNumber,
+}
+
+pub type ProvenanceRecordOf<T> = ProvenanceRecord<
+	<T as system::Trait>::AccountId,
+	<T as system::Trait>::BlockNumber,
+>;
+
+pub type ClaimInfoOf<T> = ClaimInfo<
+	<T as system::Trait>::AccountId,
+	<T as system::Trait>::BlockNumber,
+	<T as timestamp::Trait>::Moment,
+>;
+
+// A value placed in storage that represents the current version of the pallet storage.
+// This value is used by the `on_runtime_upgrade` logic to determine whether we run
+// storage migration logic.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum Releases {
+	/// `Proofs` values are `(AccountId, BlockNumber)` tuples.
+	V1,
+	/// `Proofs` values are `ClaimInfo`.
+	V2,
+	/// Storage lives under the `Poe` prefix instead of `TemplateModule`.
+	V3,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1
+	}
+}
+
 /// The pallet's configuration trait.
-pub trait Trait: system::Trait {
+pub trait Trait: system::Trait + timestamp::Trait {
 	// Add other types and constants required to configure this pallet.
 
 	/// The overarching event type.
@@ -25,23 +204,189 @@ pub trait Trait: system::Trait {
 
 	// 附加题答案
 	type MaxClaimLength: Get<u32>;
+
+	/// The maximum length of a claim note, in bytes.
+	type MaxNoteLength: Get<u32>;
+
+	/// The hasher applied on chain by `create_claim_from_content`, e.g. `BlakeTwo256`.
+	/// Its digest must fit in `MaxClaimLength`, and it must implement one of the
+	/// `HashAlgorithm`s, which is recorded with the claim.
+	type ContentHashing: HashT;
+
+	/// The maximum length of content hashed on chain, in bytes.
+	type MaxContentLength: Get<u32>;
+
+	/// The currency in which claims are bought and sold, and deposits are reserved.
+	type Currency: ReservableCurrency<Self::AccountId>;
+
+	/// The base deposit reserved from the owner of a claim.
+	type ClaimDeposit: Get<BalanceOf<Self>>;
+
+	/// The additional deposit reserved per byte of claim.
+	type ClaimDepositPerByte: Get<BalanceOf<Self>>;
+
+	/// The deposit reserved from the owner of an anchored Merkle root.
+	type RootDeposit: Get<BalanceOf<Self>>;
+
+	/// The maximum number of claims in a single batch call.
+	type MaxBatchSize: Get<u32>;
+
+	/// The maximum number of claims that may expire in the same block.
+	type MaxExpiriesPerBlock: Get<u32>;
+
+	/// The maximum number of signers of a co-signed claim.
+	type MaxSigners: Get<u32>;
+
+	/// The maximum number of provenance records kept per claim. Older records are dropped first.
+	type MaxHistoryLength: Get<u32>;
+
+	/// The maximum number of operators an owner may approve at once.
+	type MaxOperators: Get<u32>;
+
+	/// The signature with which an owner authorizes a claim submitted on their behalf.
+	type Signature: Verify<Signer = Self::Signer> + Parameter;
+
+	/// The public key behind `Signature`, identifying an account.
+	type Signer: IdentifyAccount<AccountId = Self::AccountId>;
+
+	/// Weight information for extrinsics in this pallet.
+	type WeightInfo: WeightInfo;
+
+	/// Produces valid signatures for the `create_claim_for` benchmark.
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkSigner: BenchmarkSigner<Self::AccountId, Self::Signature>;
 }
 
+type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
+
 // This pallet's storage items.
 decl_storage! {
 	// It is important to update your storage name so that your pallet's
 	// storage items are isolated from other pallets.
-	// ---------------------------------vvvvvvvvvvvvvv
-	trait Store for Module<T: Trait> as TemplateModule {
-		Proofs get(fn proofs): map hasher(blake2_128_concat) Vec<u8> => (T::AccountId, T::BlockNumber);
+	// ---------------------------------vvv
+	trait Store for Module<T: Trait> as Poe {
+		Proofs get(fn proofs): map hasher(blake2_128_concat) Vec<u8> => ClaimInfoOf<T>;
+
+		/// Claims owned by each account, keyed by (owner, position).
+		OwnedClaims get(fn owned_claim): map hasher(blake2_128_concat) (T::AccountId, u32) => Vec<u8>;
+		/// Number of claims owned by each account.
+		OwnedClaimsCount get(fn owned_claims_count): map hasher(blake2_128_concat) T::AccountId => u32;
+		/// Position of each claim in its owner's `OwnedClaims`.
+		OwnedClaimsIndex: map hasher(blake2_128_concat) Vec<u8> => u32;
+
+		/// Asking price of each claim. None means not for sale.
+		ClaimPrices get(fn claim_price): map hasher(blake2_128_concat) Vec<u8> => Option<BalanceOf<T>>;
+
+		/// Deposit reserved from the owner of each claim.
+		ClaimDeposits get(fn claim_deposit): map hasher(blake2_128_concat) Vec<u8> => BalanceOf<T>;
+
+		/// Recipient of each claim offered but not yet accepted.
+		PendingTransfers get(fn pending_transfer): map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+
+		/// Block at which each expiring claim is removed. A frozen claim that is past it is
+		/// removed once unfrozen.
+		ClaimExpiries get(fn claim_expiry): map hasher(blake2_128_concat) Vec<u8> => Option<T::BlockNumber>;
+		/// Claims to be removed at the start of each block.
+		Expiries get(fn expiries): map hasher(twox_64_concat) T::BlockNumber => Vec<Vec<u8>>;
+
+		/// Anchored Merkle roots, each committing to a batch of claims.
+		AnchoredRoots get(fn anchored_root): map hasher(blake2_128_concat) H256 => Option<RootInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+		/// Signers and endorsement progress of co-signed claims, kept after finalization.
+		CosignedClaims get(fn cosigned_claim): map hasher(blake2_128_concat) Vec<u8> => Option<CosignedClaim<T::AccountId, BalanceOf<T>>>;
+
+		/// Next nonce each account must sign for `create_claim_for`.
+		ClaimNonces get(fn claim_nonce): map hasher(blake2_128_concat) T::AccountId => u64;
+		/// Account holding the deposit of claims registered on the owner's behalf.
+		ClaimSponsors get(fn claim_sponsor): map hasher(blake2_128_concat) Vec<u8> => Option<T::AccountId>;
+
+		/// Accounts each owner has approved to manage its claims. (owner, operator)
+		Operators get(fn is_operator): double_map hasher(blake2_128_concat) T::AccountId, hasher(blake2_128_concat) T::AccountId => bool;
+		/// The number of operators each owner has approved.
+		OperatorCounts get(fn operator_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+		/// Reason code of each claim frozen by root, pending the resolution of a dispute.
+		FrozenClaims get(fn frozen_claim): map hasher(blake2_128_concat) Vec<u8> => Option<u32>;
+
+		/// Provenance history of each claim, oldest first. Kept when the claim is removed.
+		ClaimHistory get(fn claim_history): map hasher(blake2_128_concat) Vec<u8> => Vec<ProvenanceRecordOf<T>>;
+
+		/// Storage version of the pallet.
+		///
+		/// New networks start with the latest version, as determined by the genesis build.
+		StorageVersion build(|_| Releases::V3): Releases;
+	}
+	add_extra_genesis {
+		/// Claims recorded at genesis, without a deposit. (claim, owner)
+		config(claims): Vec<(Vec<u8>, T::AccountId)>;
+		build(|config: &GenesisConfig<T>| {
+			for (claim, owner) in &config.claims {
+				assert!(
+					T::MaxClaimLength::get() >= claim.len() as u32,
+					"Genesis claim is longer than MaxClaimLength",
+				);
+				assert!(!Proofs::<T>::contains_key(claim), "Duplicate genesis claim");
+
+				Module::<T>::do_insert_claim(owner, claim, Vec::new(), HashAlgorithm::default(), Zero::zero(), None);
+			}
+		});
 	}
 }
 
 // The pallet's events
 decl_event!(
-	pub enum Event<T> where AccountId = <T as system::Trait>::AccountId {
+	pub enum Event<T> where
+		AccountId = <T as system::Trait>::AccountId,
+		Balance = BalanceOf<T>,
+	{
 		ClaimCreated(AccountId, Vec<u8>),
 		ClaimRevoked(AccountId, Vec<u8>),
+		/// A claim was transferred. (from, to, claim)
+		ClaimTransferred(AccountId, AccountId, Vec<u8>),
+		/// A claim was offered to a new owner, pending acceptance. (from, to, claim)
+		ClaimOffered(AccountId, AccountId, Vec<u8>),
+		/// A pending claim offer was cancelled. (cancelled by, claim)
+		ClaimOfferCancelled(AccountId, Vec<u8>),
+		/// A claim reached its expiry block and was removed. (owner, claim)
+		ClaimExpired(AccountId, Vec<u8>),
+		/// A Merkle root of claims was anchored. (owner, root, leaf_count)
+		RootAnchored(AccountId, H256, u32),
+		/// An anchored Merkle root was removed. (owner, root)
+		RootRemoved(AccountId, H256),
+		/// A co-signed claim was opened. (creator, claim, threshold)
+		CosignedClaimOpened(AccountId, Vec<u8>, u32),
+		/// A co-signed claim was endorsed. (signer, claim, endorsements so far)
+		ClaimEndorsed(AccountId, Vec<u8>, u32),
+		/// A co-signed claim met its threshold and was recorded. (creator, claim)
+		CosignedClaimFinalized(AccountId, Vec<u8>),
+		/// A co-signed claim was cancelled before it was finalized. (creator, claim)
+		CosignedClaimCancelled(AccountId, Vec<u8>),
+		/// A claim's asking price was set. None means it was delisted. (owner, claim, price)
+		ClaimPriced(AccountId, Vec<u8>, Option<Balance>),
+		/// A claim was sold. (from, to, claim, price)
+		ClaimSold(AccountId, AccountId, Vec<u8>, Balance),
+		/// A batch of claims was created. (owner, count)
+		ClaimsCreated(AccountId, u32),
+		/// A batch of claims was revoked. (owner, count)
+		ClaimsRevoked(AccountId, u32),
+		/// A batch of claims was transferred. (from, to, count)
+		ClaimsTransferred(AccountId, AccountId, u32),
+		/// An operator was approved to manage an owner's claims. (owner, operator)
+		OperatorApproved(AccountId, AccountId),
+		/// An operator approval was revoked. (owner, operator)
+		OperatorRevoked(AccountId, AccountId),
+		/// An operator revoked a claim on behalf of its owner. (operator, owner, claim)
+		ClaimRevokedByOperator(AccountId, AccountId, Vec<u8>),
+		/// An operator transferred a claim on behalf of its owner. (operator, from, to, claim)
+		ClaimTransferredByOperator(AccountId, AccountId, AccountId, Vec<u8>),
+		/// A claim was frozen by root. (claim, reason)
+		ClaimFrozen(Vec<u8>, u32),
+		/// A frozen claim was released by root. (claim)
+		ClaimUnfrozen(Vec<u8>),
+		/// A claim was transferred by root. (from, to, claim, reason)
+		ClaimForceTransferred(AccountId, AccountId, Vec<u8>, u32),
+		/// A claim was revoked by root. (owner, claim, reason)
+		ClaimForceRevoked(AccountId, Vec<u8>, u32),
 	}
 );
 
@@ -52,6 +397,35 @@ decl_error! {
 		ClaimNotExist,
 		NotClaimOwner,
 		ProofTooLong,
+		NoteTooLong,
+		NotForSale,
+		PriceTooLow,
+		BatchTooLarge,
+		DuplicateClaim,
+		NoPendingTransfer,
+		NotTransferRecipient,
+		NotTransferParty,
+		ExpiryInPast,
+		TooManyExpiries,
+		RootAlreadyAnchored,
+		EmptyRoot,
+		RootNotAnchored,
+		NotRootOwner,
+		TooManySigners,
+		DuplicateSigner,
+		InvalidThreshold,
+		NotCosignedClaim,
+		NotSigner,
+		AlreadyEndorsed,
+		AlreadyFinalized,
+		InvalidNonce,
+		InvalidSignature,
+		NotOperator,
+		TooManyOperators,
+		ContentTooLong,
+		UnknownContentHashing,
+		ClaimFrozen,
+		ClaimNotFrozen,
 	}
 }
 
@@ -68,55 +442,1004 @@ decl_module! {
 		// this is needed only if you are using events in your pallet
 		fn deposit_event() = default;
 
-		#[weight = 0]
-		pub fn create_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+		/// The maximum length of content hashed on chain, in bytes.
+		const MaxContentLength: u32 = T::MaxContentLength::get();
+
+		/// The base deposit reserved from the owner of a claim.
+		const ClaimDeposit: BalanceOf<T> = T::ClaimDeposit::get();
+
+		/// The additional deposit reserved per byte of claim.
+		const ClaimDepositPerByte: BalanceOf<T> = T::ClaimDepositPerByte::get();
+
+		/// The deposit reserved from the owner of an anchored Merkle root.
+		const RootDeposit: BalanceOf<T> = T::RootDeposit::get();
+
+		/// The maximum number of claims in a single batch call.
+		const MaxBatchSize: u32 = T::MaxBatchSize::get();
+
+		/// The maximum number of claims that may expire in the same block.
+		const MaxExpiriesPerBlock: u32 = T::MaxExpiriesPerBlock::get();
+
+		/// The maximum number of signers of a co-signed claim.
+		const MaxSigners: u32 = T::MaxSigners::get();
+
+		/// The maximum number of provenance records kept per claim.
+		const MaxHistoryLength: u32 = T::MaxHistoryLength::get();
+
+		/// The maximum number of operators an owner may approve at once.
+		const MaxOperators: u32 = T::MaxOperators::get();
+
+		fn on_runtime_upgrade() -> Weight {
+			let mut weight = 0;
+
+			// Networks that predate the `Poe` prefix keep everything, including the storage
+			// version, under the legacy prefix.
+			if !StorageVersion::exists() {
+				weight += Self::migrate_to_poe_prefix();
+			}
+
+			weight += Self::migrate_to_claim_info();
+
+			if StorageVersion::get() == Releases::V2 {
+				StorageVersion::put(Releases::V3);
+			}
+
+			weight
+		}
+
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::remove_expired_claims(now)
+		}
+
+		#[weight = T::WeightInfo::create_claim(claim.len() as u32)]
+		pub fn create_claim(
+			origin,
+			claim: Vec<u8>,
+			note: Vec<u8>,
+			hash_algorithm: HashAlgorithm,
+			expires_at: Option<T::BlockNumber>,
+		) -> dispatch::DispatchResult {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
+			ensure!(!Self::is_claimed(&claim), Error::<T>::ProofAlreadyExist);
 
 			// 附加题答案
 			ensure!(T::MaxClaimLength::get() >= claim.len() as u32, Error::<T>::ProofTooLong);
 
-			Proofs::<T>::insert(&claim, (sender.clone(), system::Module::<T>::block_number()));
+			ensure!(T::MaxNoteLength::get() >= note.len() as u32, Error::<T>::NoteTooLong);
+
+			if let Some(expires_at) = expires_at {
+				ensure!(expires_at > system::Module::<T>::block_number(), Error::<T>::ExpiryInPast);
+				ensure!(
+					T::MaxExpiriesPerBlock::get() > Self::expiries(expires_at).len() as u32,
+					Error::<T>::TooManyExpiries
+				);
+			}
+
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit)?;
+
+			Self::do_insert_claim(&sender, &claim, note, hash_algorithm, deposit, expires_at);
 
 			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
 
 			Ok(())
 		}
 
-		#[weight = 0]
+		/// Create a claim from the digest of `content`, hashed on chain with `ContentHashing`.
+		/// Only the digest is stored
+		#[weight = T::WeightInfo::create_claim_from_content(content.len() as u32)]
+		pub fn create_claim_from_content(origin, content: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(T::MaxContentLength::get() >= content.len() as u32, Error::<T>::ContentTooLong);
+
+			let claim = T::ContentHashing::hash(&content).as_ref().to_vec();
+
+			ensure!(!Self::is_claimed(&claim), Error::<T>::ProofAlreadyExist);
+
+			ensure!(T::MaxClaimLength::get() >= claim.len() as u32, Error::<T>::ProofTooLong);
+
+			let algorithm = Self::content_hash_algorithm().ok_or(Error::<T>::UnknownContentHashing)?;
+
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit)?;
+
+			Self::do_insert_claim(&sender, &claim, Vec::new(), algorithm, deposit, None);
+
+			Self::deposit_event(RawEvent::ClaimCreated(sender, claim));
+
+			Ok(())
+		}
+
+		#[weight = T::WeightInfo::revoke_claim(claim.len() as u32)]
 		pub fn revoke_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
 			let sender = ensure_signed(origin)?;
 
 			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
 
-			let (owner, _block_number) = Proofs::<T>::get(&claim);
+			let owner = Proofs::<T>::get(&claim).owner;
 
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			Self::ensure_owner_or_operator(&sender, &owner)?;
 
-			Proofs::<T>::remove(&claim);
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
 
-			Self::deposit_event(RawEvent::ClaimRevoked(sender, claim));
+			Self::do_remove_claim(Some(&sender), &owner, &claim, ProvenanceAction::Revoked);
+
+			if sender != owner {
+				Self::deposit_event(RawEvent::ClaimRevokedByOperator(sender, owner.clone(), claim.clone()));
+			}
+			Self::deposit_event(RawEvent::ClaimRevoked(owner, claim));
 
 			Ok(())
 		}
 
 		// 第二题答案
-		#[weight = 0]
+		#[weight = T::WeightInfo::transfer_claim(claim.len() as u32)]
 		pub fn transfer_claim(origin, claim: Vec<u8>, dest: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
 			let sender = ensure_signed(origin)?;
 
 			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
 
-			let (owner, _block_number) = Proofs::<T>::get(&claim);
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			Self::ensure_owner_or_operator(&sender, &owner)?;
+
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer_claim(Some(&sender), &owner, &dest, &claim)?;
+
+			if sender != owner {
+				Self::deposit_event(RawEvent::ClaimTransferredByOperator(
+					sender,
+					owner.clone(),
+					dest.clone(),
+					claim.clone(),
+				));
+			}
+			Self::deposit_event(RawEvent::ClaimTransferred(owner, dest, claim));
+
+			Ok(())
+		}
+
+		/// Offer a claim to `dest`, who becomes the owner once they accept it
+		#[weight = T::WeightInfo::offer_claim(claim.len() as u32)]
+		pub fn offer_claim(origin, claim: Vec<u8>, dest: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			Self::ensure_owner_or_operator(&sender, &owner)?;
 
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
 
 			let dest = T::Lookup::lookup(dest)?;
 
-			Proofs::<T>::insert(&claim, (dest, system::Module::<T>::block_number()));
+			PendingTransfers::<T>::insert(&claim, &dest);
+
+			Self::deposit_event(RawEvent::ClaimOffered(owner, dest, claim));
+
+			Ok(())
+		}
+
+		/// Accept a claim offered to the sender, who puts up a fresh deposit for it
+		#[weight = T::WeightInfo::accept_claim(claim.len() as u32)]
+		pub fn accept_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let recipient = Self::pending_transfer(&claim).ok_or(Error::<T>::NoPendingTransfer)?;
+
+			ensure!(recipient == sender, Error::<T>::NotTransferRecipient);
+
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit)?;
+
+			Self::replace_deposit(&owner, &sender, &claim, deposit);
+			Self::move_claim(Some(&sender), &owner, &sender, &claim);
+
+			Self::deposit_event(RawEvent::ClaimTransferred(owner, sender, claim));
 
 			Ok(())
 		}
+
+		/// Cancel a pending claim offer. Either the owner or the recipient may cancel
+		#[weight = T::WeightInfo::cancel_claim_offer(claim.len() as u32)]
+		pub fn cancel_claim_offer(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let recipient = Self::pending_transfer(&claim).ok_or(Error::<T>::NoPendingTransfer)?;
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			ensure!(sender == owner || sender == recipient, Error::<T>::NotTransferParty);
+
+			PendingTransfers::<T>::remove(&claim);
+
+			Self::deposit_event(RawEvent::ClaimOfferCancelled(sender, claim));
+
+			Ok(())
+		}
+
+		/// Set a price for a claim for sale
+		/// None to delist the claim
+		#[weight = T::WeightInfo::set_claim_price(claim.len() as u32)]
+		pub fn set_claim_price(origin, claim: Vec<u8>, price: Option<BalanceOf<T>>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			Self::ensure_owner_or_operator(&sender, &owner)?;
+
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+
+			ClaimPrices::<T>::mutate_exists(&claim, |p| *p = price);
+
+			Self::deposit_event(RawEvent::ClaimPriced(owner, claim, price));
+
+			Ok(())
+		}
+
+		/// Buy a claim at its asking price, as long as it does not exceed `max_price`
+		#[weight = T::WeightInfo::buy_claim(claim.len() as u32)]
+		pub fn buy_claim(origin, claim: Vec<u8>, max_price: BalanceOf<T>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+
+			let price = Self::claim_price(&claim).ok_or(Error::<T>::NotForSale)?;
+
+			ensure!(max_price >= price, Error::<T>::PriceTooLow);
+
+			// The buyer always puts up a fresh deposit, and the previous holder gets theirs back.
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit)?;
+
+			if let Err(e) = T::Currency::transfer(&sender, &owner, price, ExistenceRequirement::KeepAlive) {
+				T::Currency::unreserve(&sender, deposit);
+				return Err(e);
+			}
+
+			Self::replace_deposit(&owner, &sender, &claim, deposit);
+			Self::move_claim(Some(&sender), &owner, &sender, &claim);
+
+			Self::deposit_event(RawEvent::ClaimSold(owner, sender, claim, price));
+
+			Ok(())
+		}
+
+		/// Create a batch of claims. Either all of them are created or none is.
+		#[weight = T::WeightInfo::create_claim(T::MaxClaimLength::get()).saturating_mul(claims.len() as Weight)]
+		pub fn create_claims(origin, claims: Vec<Vec<u8>>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(T::MaxBatchSize::get() >= claims.len() as u32, Error::<T>::BatchTooLarge);
+			Self::ensure_unique(&claims)?;
+
+			let mut deposit = BalanceOf::<T>::zero();
+			for claim in &claims {
+				ensure!(!Self::is_claimed(claim), Error::<T>::ProofAlreadyExist);
+				ensure!(T::MaxClaimLength::get() >= claim.len() as u32, Error::<T>::ProofTooLong);
+
+				deposit = deposit.saturating_add(Self::deposit_for(claim));
+			}
+
+			T::Currency::reserve(&sender, deposit)?;
+
+			for claim in &claims {
+				Self::do_insert_claim(&sender, claim, Vec::new(), HashAlgorithm::default(), Self::deposit_for(claim), None);
+			}
+
+			Self::deposit_event(RawEvent::ClaimsCreated(sender, claims.len() as u32));
+
+			Ok(())
+		}
+
+		/// Revoke a batch of claims. Either all of them are revoked or none is.
+		#[weight = T::WeightInfo::revoke_claim(T::MaxClaimLength::get()).saturating_mul(claims.len() as Weight)]
+		pub fn revoke_claims(origin, claims: Vec<Vec<u8>>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(T::MaxBatchSize::get() >= claims.len() as u32, Error::<T>::BatchTooLarge);
+			Self::ensure_unique(&claims)?;
+
+			let mut owners = Vec::with_capacity(claims.len());
+			for claim in &claims {
+				ensure!(Proofs::<T>::contains_key(claim), Error::<T>::ClaimNotExist);
+				let owner = Proofs::<T>::get(claim).owner;
+				Self::ensure_owner_or_operator(&sender, &owner)?;
+				ensure!(!Self::is_frozen(claim), Error::<T>::ClaimFrozen);
+
+				owners.push(owner);
+			}
+
+			for (claim, owner) in claims.iter().zip(&owners) {
+				Self::do_remove_claim(Some(&sender), owner, claim, ProvenanceAction::Revoked);
+			}
+
+			Self::deposit_event(RawEvent::ClaimsRevoked(sender, claims.len() as u32));
+
+			Ok(())
+		}
+
+		/// Transfer a batch of claims to `dest`. Either all of them are transferred or none is.
+		#[weight = T::WeightInfo::transfer_claim(T::MaxClaimLength::get()).saturating_mul(claims.len() as Weight)]
+		pub fn transfer_claims(origin, claims: Vec<Vec<u8>>, dest: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(T::MaxBatchSize::get() >= claims.len() as u32, Error::<T>::BatchTooLarge);
+			Self::ensure_unique(&claims)?;
+
+			let dest = T::Lookup::lookup(dest)?;
+
+			// Claims may belong to several owners approving the sender, so deposits are
+			// repatriated per owner.
+			let mut owners = Vec::with_capacity(claims.len());
+			let mut deposits = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+			for claim in &claims {
+				ensure!(Proofs::<T>::contains_key(claim), Error::<T>::ClaimNotExist);
+				let owner = Proofs::<T>::get(claim).owner;
+				Self::ensure_owner_or_operator(&sender, &owner)?;
+				ensure!(!Self::is_frozen(claim), Error::<T>::ClaimFrozen);
+
+				let deposit = deposits.entry(owner.clone()).or_insert_with(Zero::zero);
+				*deposit = deposit.saturating_add(Self::owner_deposit(claim));
+				owners.push(owner);
+			}
+
+			// Only the first non-zero repatriation can fail, as it makes `dest` a live account.
+			for (owner, deposit) in deposits.into_iter().filter(|(_, deposit)| !deposit.is_zero()) {
+				T::Currency::repatriate_reserved(&owner, &dest, deposit, BalanceStatus::Reserved)?;
+			}
+
+			for (claim, owner) in claims.iter().zip(&owners) {
+				Self::move_claim(Some(&sender), owner, &dest, claim);
+			}
+
+			Self::deposit_event(RawEvent::ClaimsTransferred(sender, dest, claims.len() as u32));
+
+			Ok(())
+		}
+
+		/// Anchor the Merkle root of a batch of claims. See `verify_inclusion` for the tree layout.
+		/// `RootDeposit` is reserved until the root is removed
+		#[weight = T::WeightInfo::anchor_root()]
+		pub fn anchor_root(origin, root: H256, leaf_count: u32) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(leaf_count > 0, Error::<T>::EmptyRoot);
+
+			ensure!(!AnchoredRoots::<T>::contains_key(&root), Error::<T>::RootAlreadyAnchored);
+
+			let deposit = T::RootDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+
+			AnchoredRoots::<T>::insert(&root, RootInfo {
+				owner: sender.clone(),
+				leaf_count,
+				deposit,
+				block_number: system::Module::<T>::block_number(),
+			});
+
+			Self::deposit_event(RawEvent::RootAnchored(sender, root, leaf_count));
+
+			Ok(())
+		}
+
+		/// Remove an anchored Merkle root and get its deposit back
+		#[weight = T::WeightInfo::remove_root()]
+		pub fn remove_root(origin, root: H256) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let info = Self::anchored_root(&root).ok_or(Error::<T>::RootNotAnchored)?;
+
+			ensure!(info.owner == sender, Error::<T>::NotRootOwner);
+
+			T::Currency::unreserve(&sender, info.deposit);
+			AnchoredRoots::<T>::remove(&root);
+
+			Self::deposit_event(RawEvent::RootRemoved(sender, root));
+
+			Ok(())
+		}
+
+		/// Open a claim that is recorded once `threshold` of `signers` have endorsed it
+		#[weight = T::WeightInfo::open_cosigned_claim(signers.len() as u32)]
+		pub fn open_cosigned_claim(origin, claim: Vec<u8>, signers: Vec<T::AccountId>, threshold: u32) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!Self::is_claimed(&claim), Error::<T>::ProofAlreadyExist);
+
+			ensure!(T::MaxClaimLength::get() >= claim.len() as u32, Error::<T>::ProofTooLong);
+
+			ensure!(T::MaxSigners::get() >= signers.len() as u32, Error::<T>::TooManySigners);
+
+			ensure!(threshold > 0 && threshold <= signers.len() as u32, Error::<T>::InvalidThreshold);
+
+			let mut sorted = signers.clone();
+			sorted.sort_unstable();
+			sorted.dedup();
+			ensure!(sorted.len() == signers.len(), Error::<T>::DuplicateSigner);
+
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit)?;
+
+			CosignedClaims::<T>::insert(&claim, CosignedClaim {
+				creator: sender.clone(),
+				signers,
+				threshold,
+				endorsements: Vec::new(),
+				deposit,
+				finalized: false,
+			});
+
+			Self::deposit_event(RawEvent::CosignedClaimOpened(sender, claim, threshold));
+
+			Ok(())
+		}
+
+		/// Endorse a co-signed claim. The claim is recorded once its threshold is met
+		#[weight = T::WeightInfo::endorse_claim(T::MaxSigners::get())]
+		pub fn endorse_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let mut cosigned = Self::cosigned_claim(&claim).ok_or(Error::<T>::NotCosignedClaim)?;
+
+			ensure!(!cosigned.finalized, Error::<T>::AlreadyFinalized);
+
+			ensure!(cosigned.signers.contains(&sender), Error::<T>::NotSigner);
+
+			ensure!(!cosigned.endorsements.contains(&sender), Error::<T>::AlreadyEndorsed);
+
+			cosigned.endorsements.push(sender.clone());
+
+			let endorsements = cosigned.endorsements.len() as u32;
+
+			Self::deposit_event(RawEvent::ClaimEndorsed(sender, claim.clone(), endorsements));
+
+			if endorsements >= cosigned.threshold {
+				cosigned.finalized = true;
+
+				Self::do_insert_claim(&cosigned.creator, &claim, Vec::new(), HashAlgorithm::default(), cosigned.deposit, None);
+
+				Self::deposit_event(RawEvent::CosignedClaimFinalized(cosigned.creator.clone(), claim.clone()));
+			}
+
+			CosignedClaims::<T>::insert(&claim, cosigned);
+
+			Ok(())
+		}
+
+		/// Cancel a co-signed claim that has not been finalized yet
+		#[weight = 0]
+		pub fn cancel_cosigned_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let cosigned = Self::cosigned_claim(&claim).ok_or(Error::<T>::NotCosignedClaim)?;
+
+			ensure!(cosigned.creator == sender, Error::<T>::NotClaimOwner);
+
+			ensure!(!cosigned.finalized, Error::<T>::AlreadyFinalized);
+
+			T::Currency::unreserve(&sender, cosigned.deposit);
+
+			CosignedClaims::<T>::remove(&claim);
+
+			Self::deposit_event(RawEvent::CosignedClaimCancelled(sender, claim));
+
+			Ok(())
+		}
+
+		/// Approve `operator` to manage the sender's claims, up to `MaxOperators` at once
+		#[weight = T::WeightInfo::approve_operator()]
+		pub fn approve_operator(origin, operator: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let operator = T::Lookup::lookup(operator)?;
+
+			if !Self::is_operator(&sender, &operator) {
+				let count = Self::operator_count(&sender);
+				ensure!(T::MaxOperators::get() > count, Error::<T>::TooManyOperators);
+
+				Operators::<T>::insert(&sender, &operator, true);
+				OperatorCounts::<T>::insert(&sender, count + 1);
+			}
+
+			Self::deposit_event(RawEvent::OperatorApproved(sender, operator));
+
+			Ok(())
+		}
+
+		/// Revoke the approval of `operator`
+		#[weight = T::WeightInfo::revoke_operator()]
+		pub fn revoke_operator(origin, operator: <T::Lookup as StaticLookup>::Source) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			let operator = T::Lookup::lookup(operator)?;
+
+			ensure!(Self::is_operator(&sender, &operator), Error::<T>::NotOperator);
+
+			Operators::<T>::remove(&sender, &operator);
+			OperatorCounts::<T>::mutate_exists(&sender, |count| {
+				*count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+			});
+
+			Self::deposit_event(RawEvent::OperatorRevoked(sender, operator));
+
+			Ok(())
+		}
+
+		/// Freeze a disputed claim, so its owner can no longer revoke, transfer or sell it
+		#[weight = 0]
+		pub fn freeze_claim(origin, claim: Vec<u8>, reason: u32) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			FrozenClaims::insert(&claim, reason);
+
+			Self::deposit_event(RawEvent::ClaimFrozen(claim, reason));
+
+			Ok(())
+		}
+
+		/// Release a frozen claim. A claim that expired while frozen is removed right away
+		#[weight = 0]
+		pub fn unfreeze_claim(origin, claim: Vec<u8>) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(Self::is_frozen(&claim), Error::<T>::ClaimNotFrozen);
+
+			FrozenClaims::remove(&claim);
+
+			Self::deposit_event(RawEvent::ClaimUnfrozen(claim.clone()));
+
+			let now = system::Module::<T>::block_number();
+			if Self::claim_expiry(&claim).map_or(false, |expires_at| expires_at <= now) {
+				let owner = Proofs::<T>::get(&claim).owner;
+				Self::do_remove_claim(None, &owner, &claim, ProvenanceAction::Expired);
+
+				Self::deposit_event(RawEvent::ClaimExpired(owner, claim));
+			}
+
+			Ok(())
+		}
+
+		/// Transfer a claim to `dest` regardless of its owner, e.g. to settle a dispute.
+		/// The deposit follows the claim. A frozen claim stays frozen
+		#[weight = 0]
+		pub fn force_transfer_claim(
+			origin,
+			claim: Vec<u8>,
+			dest: <T::Lookup as StaticLookup>::Source,
+			reason: u32,
+		) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			let dest = T::Lookup::lookup(dest)?;
+
+			Self::do_transfer_claim(None, &owner, &dest, &claim)?;
+
+			Self::deposit_event(RawEvent::ClaimForceTransferred(owner, dest, claim, reason));
+
+			Ok(())
+		}
+
+		/// Revoke a claim regardless of its owner, who gets the deposit back
+		#[weight = 0]
+		pub fn force_revoke_claim(origin, claim: Vec<u8>, reason: u32) -> dispatch::DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ClaimNotExist);
+
+			let owner = Proofs::<T>::get(&claim).owner;
+
+			Self::do_remove_claim(None, &owner, &claim, ProvenanceAction::Revoked);
+
+			Self::deposit_event(RawEvent::ClaimForceRevoked(owner, claim, reason));
+
+			Ok(())
+		}
+
+		/// Create a claim on behalf of `owner`, who signed `create_claim_for_payload(claim, nonce)`.
+		/// The sender pays the deposit and gets it back when the claim is removed
+		#[weight = T::WeightInfo::create_claim_for(claim.len() as u32)]
+		pub fn create_claim_for(
+			origin,
+			owner: T::AccountId,
+			claim: Vec<u8>,
+			signature: T::Signature,
+			nonce: u64,
+		) -> dispatch::DispatchResult {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(Self::claim_nonce(&owner) == nonce, Error::<T>::InvalidNonce);
+
+			let payload = Self::create_claim_for_payload(&claim, nonce);
+			ensure!(signature.verify(&payload[..], &owner), Error::<T>::InvalidSignature);
+
+			ensure!(!Self::is_claimed(&claim), Error::<T>::ProofAlreadyExist);
+
+			ensure!(T::MaxClaimLength::get() >= claim.len() as u32, Error::<T>::ProofTooLong);
+
+			let deposit = Self::deposit_for(&claim);
+			T::Currency::reserve(&sender, deposit)?;
+
+			ClaimNonces::<T>::insert(&owner, nonce.saturating_add(1));
+
+			Self::do_insert_claim(&owner, &claim, Vec::new(), HashAlgorithm::default(), deposit, None);
+			if sender != owner {
+				ClaimSponsors::<T>::insert(&claim, &sender);
+			}
+
+			Self::deposit_event(RawEvent::ClaimCreated(owner, claim));
+
+			Ok(())
+		}
+	}
+}
+
+impl<T: Trait> Module<T> {
+	/// The owner and metadata of `claim`, if it exists.
+	pub fn claim_of(claim: &[u8]) -> Option<ClaimInfoOf<T>> {
+		if Proofs::<T>::contains_key(claim) {
+			Some(Proofs::<T>::get(claim))
+		} else {
+			None
+		}
+	}
+
+	/// The payload an owner signs to authorize `create_claim_for`. It commits to the genesis
+	/// hash so that a signature cannot be replayed on another chain.
+	pub fn create_claim_for_payload(claim: &[u8], nonce: u64) -> Vec<u8> {
+		let genesis_hash = system::Module::<T>::block_hash(T::BlockNumber::zero());
+		(CREATE_CLAIM_FOR_CONTEXT, genesis_hash, claim, nonce).encode()
+	}
+
+	/// Claims owned by `owner`, `CLAIMS_PER_PAGE` at a time. Pages start from 0.
+	pub fn claims_of(owner: &T::AccountId, page: u32) -> Vec<Vec<u8>> {
+		let count = Self::owned_claims_count(owner);
+		let start = page.saturating_mul(CLAIMS_PER_PAGE).min(count);
+		let end = start.saturating_add(CLAIMS_PER_PAGE).min(count);
+
+		(start..end).map(|index| Self::owned_claim((owner, index))).collect()
+	}
+
+	/// Whether `leaf` is committed to by the anchored Merkle root `root`.
+	///
+	/// Leaves are hashed as blake2-256 of `MERKLE_LEAF_PREFIX ++ leaf`, and each parent as
+	/// blake2-256 of `MERKLE_NODE_PREFIX` followed by its two children in ascending order, so
+	/// `proof` only needs the sibling hashes from the leaf up to the root. The prefixes keep an
+	/// inner node from being passed off as a leaf, and `proof` may not be deeper than a tree
+	/// of the root's `leaf_count`.
+	pub fn verify_inclusion(root: H256, leaf: &[u8], proof: &[H256]) -> bool {
+		let info = match Self::anchored_root(&root) {
+			Some(info) => info,
+			None => return false,
+		};
+
+		// The depth of a tree of `leaf_count` leaves, i.e. ceil(log2(leaf_count)).
+		let depth = 32 - info.leaf_count.saturating_sub(1).leading_zeros();
+		if proof.len() > depth as usize {
+			return false;
+		}
+
+		let leaf_hash = H256::from(blake2_256(&[MERKLE_LEAF_PREFIX, leaf].concat()));
+		let computed = proof.iter().fold(leaf_hash, |node, sibling| {
+			let (left, right) = if node <= *sibling { (node, *sibling) } else { (*sibling, node) };
+			H256::from(blake2_256(&[MERKLE_NODE_PREFIX, left.as_bytes(), right.as_bytes()].concat()))
+		});
+
+		computed == root
+	}
+
+	/// Moves this pallet's storage from `LEGACY_PREFIX` to `PREFIX`.
+	///
+	/// Keys are moved as raw bytes, so the values keep whatever format they had.
+	fn migrate_to_poe_prefix() -> Weight {
+		let mut moved: Weight = 0;
+
+		for item in LEGACY_ITEMS {
+			let old_prefix = [twox_128(LEGACY_PREFIX), twox_128(item)].concat();
+			let new_prefix = [twox_128(PREFIX), twox_128(item)].concat();
+
+			// Value items live at the prefix itself, map entries below it.
+			let mut next = Some(old_prefix.clone());
+			while let Some(key) = next {
+				if !key.starts_with(&old_prefix) {
+					break;
+				}
+
+				if let Some(value) = sp_io::storage::get(&key) {
+					let new_key = [&new_prefix[..], &key[old_prefix.len()..]].concat();
+					sp_io::storage::set(&new_key, &value);
+					sp_io::storage::clear(&key);
+					moved += 1;
+				}
+
+				next = sp_io::storage::next_key(&key);
+			}
+		}
+
+		T::DbWeight::get().reads_writes(moved + LEGACY_ITEMS.len() as Weight, 2 * moved)
+	}
+
+	/// Converts `Proofs` values from `(AccountId, BlockNumber)` tuples to `ClaimInfo`.
+	///
+	/// The creation timestamp of migrated claims is unknown and left at zero.
+	fn migrate_to_claim_info() -> Weight {
+		if StorageVersion::get() != Releases::V1 {
+			return 0;
+		}
+
+		Proofs::<T>::translate(|_claim, (owner, block_number): (T::AccountId, T::BlockNumber)| {
+			Some(ClaimInfo {
+				owner,
+				block_number,
+				created_at: Default::default(),
+				note: Vec::new(),
+				hash_algorithm: HashAlgorithm::default(),
+			})
+		});
+
+		// Claims created before the owner index existed were never added to it.
+		for (claim, info) in Proofs::<T>::iter() {
+			if !Self::is_owned_claim_indexed(&info.owner, &claim) {
+				Self::insert_owned_claim(&info.owner, &claim);
+			}
+		}
+
+		StorageVersion::put(Releases::V2);
+
+		T::MaximumBlockWeight::get()
+	}
+
+	/// The deposit reserved for a claim of this length.
+	pub fn deposit_for(claim: &[u8]) -> BalanceOf<T> {
+		let per_byte = T::ClaimDepositPerByte::get().saturating_mul((claim.len() as u32).into());
+		T::ClaimDeposit::get().saturating_add(per_byte)
+	}
+
+	fn ensure_unique(claims: &[Vec<u8>]) -> dispatch::DispatchResult {
+		let mut sorted = claims.to_vec();
+		sorted.sort_unstable();
+		sorted.dedup();
+
+		ensure!(sorted.len() == claims.len(), Error::<T>::DuplicateClaim);
+
+		Ok(())
+	}
+
+	fn do_insert_claim(
+		owner: &T::AccountId,
+		claim: &[u8],
+		note: Vec<u8>,
+		hash_algorithm: HashAlgorithm,
+		deposit: BalanceOf<T>,
+		expires_at: Option<T::BlockNumber>,
+	) {
+		Proofs::<T>::insert(claim, ClaimInfo {
+			owner: owner.clone(),
+			block_number: system::Module::<T>::block_number(),
+			created_at: timestamp::Module::<T>::get(),
+			note,
+			hash_algorithm,
+		});
+		ClaimDeposits::<T>::insert(claim, deposit);
+		Self::insert_owned_claim(owner, claim);
+		Self::record_provenance(claim, ProvenanceAction::Created, Some(owner), owner);
+
+		if let Some(expires_at) = expires_at {
+			ClaimExpiries::<T>::insert(claim, expires_at);
+			Expiries::<T>::mutate(expires_at, |claims| claims.push(claim.to_vec()));
+		}
+	}
+
+	/// Ensures `sender` is `owner` or one of its approved operators.
+	fn ensure_owner_or_operator(sender: &T::AccountId, owner: &T::AccountId) -> dispatch::DispatchResult {
+		ensure!(sender == owner || Self::is_operator(owner, sender), Error::<T>::NotClaimOwner);
+
+		Ok(())
+	}
+
+	/// Whether `claim` has been frozen by root.
+	fn is_frozen(claim: &[u8]) -> bool {
+		FrozenClaims::contains_key(claim)
+	}
+
+	/// The `HashAlgorithm` that `ContentHashing` implements, if it is a known one.
+	fn content_hash_algorithm() -> Option<HashAlgorithm> {
+		let sample = b"poe:content_hash_algorithm";
+		let digest = T::ContentHashing::hash(sample);
+		[HashAlgorithm::Blake2_256, HashAlgorithm::Sha2_256, HashAlgorithm::Keccak256]
+			.iter()
+			.copied()
+			.find(|algorithm| digest.as_ref() == &algorithm.hash(sample)[..])
+	}
+
+	/// Whether `claim` is recorded, or reserved by a co-signed claim awaiting endorsements.
+	fn is_claimed(claim: &[u8]) -> bool {
+		Proofs::<T>::contains_key(claim) || CosignedClaims::<T>::contains_key(claim)
+	}
+
+	/// The part of a claim's deposit that is held by its owner and moves with the claim.
+	fn owner_deposit(claim: &[u8]) -> BalanceOf<T> {
+		if ClaimSponsors::<T>::contains_key(claim) {
+			Zero::zero()
+		} else {
+			Self::claim_deposit(claim)
+		}
+	}
+
+	/// The account holding the deposit of `claim`, owned by `owner`.
+	fn deposit_holder(owner: &T::AccountId, claim: &[u8]) -> T::AccountId {
+		Self::claim_sponsor(claim).unwrap_or_else(|| owner.clone())
+	}
+
+	/// Makes `to` the holder of the deposit of `claim`, owned by `from`, once `to` has
+	/// reserved `deposit`. The previous holder gets their deposit back.
+	fn replace_deposit(from: &T::AccountId, to: &T::AccountId, claim: &[u8], deposit: BalanceOf<T>) {
+		let holder = Self::deposit_holder(from, claim);
+		T::Currency::unreserve(&holder, ClaimDeposits::<T>::get(claim));
+
+		ClaimDeposits::<T>::insert(claim, deposit);
+		ClaimSponsors::<T>::remove(claim);
+	}
+
+	fn do_remove_claim(
+		actor: Option<&T::AccountId>,
+		owner: &T::AccountId,
+		claim: &[u8],
+		action: ProvenanceAction,
+	) {
+		let holder = ClaimSponsors::<T>::take(claim).unwrap_or_else(|| owner.clone());
+		T::Currency::unreserve(&holder, ClaimDeposits::<T>::take(claim));
+
+		Proofs::<T>::remove(claim);
+		ClaimPrices::<T>::remove(claim);
+		PendingTransfers::<T>::remove(claim);
+		CosignedClaims::<T>::remove(claim);
+		FrozenClaims::remove(claim);
+		Self::remove_owned_claim(owner, claim);
+		Self::record_provenance(claim, action, actor, owner);
+
+		if let Some(expires_at) = ClaimExpiries::<T>::take(claim) {
+			Expiries::<T>::mutate_exists(expires_at, |claims| {
+				if let Some(list) = claims {
+					list.retain(|c| c.as_slice() != claim);
+					if list.is_empty() {
+						*claims = None;
+					}
+				}
+			});
+		}
+	}
+
+	/// Removes every claim that expires at `now`. A frozen claim is kept while its dispute
+	/// is settled, and keeps its expiry so that `unfreeze_claim` removes it.
+	fn remove_expired_claims(now: T::BlockNumber) -> Weight {
+		let expired = Expiries::<T>::take(now);
+
+		for claim in &expired {
+			if Self::is_frozen(claim) {
+				continue;
+			}
+
+			// Already dequeued, so `do_remove_claim` must not touch `Expiries` again.
+			ClaimExpiries::<T>::remove(claim);
+
+			let owner = Proofs::<T>::get(claim).owner;
+			Self::do_remove_claim(None, &owner, claim, ProvenanceAction::Expired);
+
+			Self::deposit_event(RawEvent::ClaimExpired(owner, claim.clone()));
+		}
+
+		let count = expired.len() as Weight;
+		T::DbWeight::get().reads_writes(1 + 5 * count, 1 + 9 * count)
+	}
+
+	fn do_transfer_claim(
+		actor: Option<&T::AccountId>,
+		from: &T::AccountId,
+		to: &T::AccountId,
+		claim: &[u8],
+	) -> dispatch::DispatchResult {
+		// The deposit follows the claim, so the new owner gets it back on revoke. The
+		// recipient never has to put up funds it did not agree to.
+		T::Currency::repatriate_reserved(from, to, Self::owner_deposit(claim), BalanceStatus::Reserved)?;
+
+		Self::move_claim(actor, from, to, claim);
+
+		Ok(())
+	}
+
+	fn move_claim(actor: Option<&T::AccountId>, from: &T::AccountId, to: &T::AccountId, claim: &[u8]) {
+		Proofs::<T>::mutate(claim, |info| {
+			info.owner = to.clone();
+			info.block_number = system::Module::<T>::block_number();
+		});
+		ClaimPrices::<T>::remove(claim);
+		PendingTransfers::<T>::remove(claim);
+		Self::remove_owned_claim(from, claim);
+		Self::insert_owned_claim(to, claim);
+		Self::record_provenance(claim, ProvenanceAction::Transferred, actor, to);
+	}
+
+	/// Appends to the provenance history of `claim`, dropping the oldest records beyond
+	/// `MaxHistoryLength`.
+	fn record_provenance(
+		claim: &[u8],
+		action: ProvenanceAction,
+		actor: Option<&T::AccountId>,
+		owner: &T::AccountId,
+	) {
+		let max = T::MaxHistoryLength::get() as usize;
+		if max == 0 {
+			return;
+		}
+
+		ClaimHistory::<T>::mutate(claim, |history| {
+			if history.len() >= max {
+				history.drain(..=history.len() - max);
+			}
+			history.push(ProvenanceRecord {
+				action,
+				actor: actor.cloned(),
+				owner: owner.clone(),
+				block_number: system::Module::<T>::block_number(),
+			});
+		});
+	}
+
+	fn insert_owned_claim(owner: &T::AccountId, claim: &[u8]) {
+		let count = Self::owned_claims_count(owner);
+
+		OwnedClaims::<T>::insert((owner, count), claim);
+		OwnedClaimsIndex::insert(claim, count);
+		OwnedClaimsCount::<T>::insert(owner, count + 1);
+	}
+
+	/// Whether `claim` is in `owner`'s `OwnedClaims`.
+	fn is_owned_claim_indexed(owner: &T::AccountId, claim: &[u8]) -> bool {
+		let index = OwnedClaimsIndex::get(claim);
+
+		index < Self::owned_claims_count(owner) && Self::owned_claim((owner, index)) == claim
+	}
+
+	fn remove_owned_claim(owner: &T::AccountId, claim: &[u8]) {
+		// An unindexed claim would otherwise evict whichever claim sits at its default position.
+		if !Self::is_owned_claim_indexed(owner, claim) {
+			return;
+		}
+
+		let index = OwnedClaimsIndex::take(claim);
+		let last = Self::owned_claims_count(owner).saturating_sub(1);
+
+		// Swap the last claim into the freed slot so positions stay contiguous.
+		if index != last {
+			let last_claim = OwnedClaims::<T>::get((owner, last));
+			OwnedClaims::<T>::insert((owner, index), &last_claim);
+			OwnedClaimsIndex::insert(&last_claim, index);
+		}
+
+		OwnedClaims::<T>::remove((owner, last));
+		OwnedClaimsCount::<T>::insert(owner, last);
 	}
 }
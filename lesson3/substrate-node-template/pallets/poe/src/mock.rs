@@ -2,9 +2,9 @@
 
 use crate::{Module, Trait};
 use sp_core::H256;
-use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use frame_support::{impl_outer_origin, impl_outer_event, parameter_types, weights::Weight};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
+	traits::{BlakeTwo256, IdentityLookup}, testing::{Header, TestSignature, UintAuthorityId}, Perbill,
 };
 use frame_system as system;
 
@@ -12,10 +12,22 @@ impl_outer_origin! {
 	pub enum Origin for Test {}
 }
 
+mod poe {
+	pub use crate::Event;
+}
+
+impl_outer_event! {
+	pub enum TestEvent for Test {
+		system<T>,
+		pallet_balances<T>,
+		poe<T>,
+	}
+}
+
 // For testing the pallet, we construct most of a mock runtime. This means
 // first constructing a configuration type (`Test`) which `impl`s each of the
 // configuration traits of pallets we want to use.
-#[derive(Clone, Eq, PartialEq)]
+#[derive(Clone, Eq, PartialEq, Debug)]
 pub struct Test;
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
@@ -33,7 +45,7 @@ impl system::Trait for Test {
 	type AccountId = u64;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
-	type Event = ();
+	type Event = TestEvent;
 	type BlockHashCount = BlockHashCount;
 	type MaximumBlockWeight = MaximumBlockWeight;
 	type DbWeight = ();
@@ -44,22 +56,86 @@ impl system::Trait for Test {
 	type AvailableBlockRatio = AvailableBlockRatio;
 	type Version = ();
 	type ModuleToIndex = ();
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 }
 
 parameter_types! {
-	pub const MaxClaimLength: u32 = 6;
+	pub const ExistentialDeposit: u64 = 1;
+}
+impl pallet_balances::Trait for Test {
+	type Balance = u64;
+	type Event = TestEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+}
+
+parameter_types! {
+	pub const MinimumPeriod: u64 = 1;
+}
+impl pallet_timestamp::Trait for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+}
+
+parameter_types! {
+	pub const MaxClaimLength: u32 = 32;
+	pub const MaxNoteLength: u32 = 8;
+	pub const MaxContentLength: u32 = 64;
+	pub const ClaimDeposit: u64 = 2;
+	pub const ClaimDepositPerByte: u64 = 1;
+	pub const RootDeposit: u64 = 5;
+	pub const MaxBatchSize: u32 = 3;
+	pub const MaxExpiriesPerBlock: u32 = 2;
+	pub const MaxSigners: u32 = 3;
+	pub const MaxHistoryLength: u32 = 3;
+	pub const MaxOperators: u32 = 2;
 }
 impl Trait for Test {
-	type Event = ();
+	type Event = TestEvent;
 	type MaxClaimLength = MaxClaimLength;
+	type MaxNoteLength = MaxNoteLength;
+	type ContentHashing = BlakeTwo256;
+	type MaxContentLength = MaxContentLength;
+	type Currency = Balances;
+	type ClaimDeposit = ClaimDeposit;
+	type ClaimDepositPerByte = ClaimDepositPerByte;
+	type RootDeposit = RootDeposit;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type MaxSigners = MaxSigners;
+	type MaxHistoryLength = MaxHistoryLength;
+	type MaxOperators = MaxOperators;
+	type Signature = TestSignature;
+	type Signer = UintAuthorityId;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkSigner = TestBenchmarkSigner;
+}
+
+/// Signs as account 4, which has no balance.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct TestBenchmarkSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkSigner<u64, TestSignature> for TestBenchmarkSigner {
+	fn sign(payload: &[u8]) -> (u64, TestSignature) {
+		(4, TestSignature(4, payload.to_vec()))
+	}
 }
 pub type PoeModule = Module<Test>;
+pub type System = system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type Timestamp = pallet_timestamp::Module<Test>;
 
 // This function basically just builds a genesis storage key/value store according to
 // our desired mockup.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1000), (2, 1000), (3, 1000)],
+	}.assimilate_storage(&mut t).unwrap();
+	t.into()
 }
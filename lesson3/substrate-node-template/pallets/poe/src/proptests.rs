@@ -0,0 +1,118 @@
+// Property-based tests driving random sequences of claim calls against an in-memory model
+
+use crate::{Error, HashAlgorithm, Proofs, mock::*};
+use frame_support::{dispatch::DispatchResult, storage::IterableStorageMap, traits::Get};
+use proptest::{collection::vec as prop_vec, prelude::*};
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug)]
+enum Op {
+	Create(u64, Vec<u8>),
+	Revoke(u64, Vec<u8>),
+	Transfer(u64, Vec<u8>, u64),
+}
+
+fn account() -> impl Strategy<Value = u64> {
+	1..=3u64
+}
+
+// A small alphabet so that calls often hit the same claims, with lengths up to one past the limit.
+fn claim() -> impl Strategy<Value = Vec<u8>> {
+	prop_vec(0..2u8, 0..=MaxClaimLength::get() as usize + 1)
+}
+
+fn op() -> impl Strategy<Value = Op> {
+	prop_oneof![
+		(account(), claim()).prop_map(|(who, claim)| Op::Create(who, claim)),
+		(account(), claim()).prop_map(|(who, claim)| Op::Revoke(who, claim)),
+		(account(), claim(), account()).prop_map(|(who, claim, dest)| Op::Transfer(who, claim, dest)),
+	]
+}
+
+/// Applies `op` to the model and returns the result the pallet is expected to produce.
+fn apply(model: &mut BTreeMap<Vec<u8>, u64>, op: &Op) -> DispatchResult {
+	match op {
+		Op::Create(who, claim) => {
+			if model.contains_key(claim) {
+				return Err(Error::<Test>::ProofAlreadyExist.into());
+			}
+			if claim.len() as u32 > MaxClaimLength::get() {
+				return Err(Error::<Test>::ProofTooLong.into());
+			}
+			// Long claims carry large deposits, so an account can run out of funds over a run.
+			if Balances::free_balance(*who) < PoeModule::deposit_for(claim) {
+				return Err(pallet_balances::Error::<Test>::InsufficientBalance.into());
+			}
+			model.insert(claim.clone(), *who);
+		}
+		Op::Revoke(who, claim) => {
+			match model.get(claim) {
+				None => return Err(Error::<Test>::ClaimNotExist.into()),
+				Some(owner) if owner != who => return Err(Error::<Test>::NotClaimOwner.into()),
+				Some(_) => { model.remove(claim); }
+			}
+		}
+		Op::Transfer(who, claim, dest) => {
+			match model.get(claim) {
+				None => return Err(Error::<Test>::ClaimNotExist.into()),
+				Some(owner) if owner != who => return Err(Error::<Test>::NotClaimOwner.into()),
+				Some(_) => { model.insert(claim.clone(), *dest); }
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn dispatch(op: &Op) -> DispatchResult {
+	match op {
+		Op::Create(who, claim) => PoeModule::create_claim(
+			Origin::signed(*who),
+			claim.clone(),
+			Vec::new(),
+			HashAlgorithm::Blake2_256,
+			None,
+		),
+		Op::Revoke(who, claim) => PoeModule::revoke_claim(Origin::signed(*who), claim.clone()),
+		Op::Transfer(who, claim, dest) => PoeModule::transfer_claim(Origin::signed(*who), claim.clone(), *dest),
+	}
+}
+
+fn check_invariants(model: &BTreeMap<Vec<u8>, u64>) -> Result<(), TestCaseError> {
+	let proofs: BTreeMap<Vec<u8>, u64> = Proofs::<Test>::iter().map(|(claim, info)| (claim, info.owner)).collect();
+
+	// Every claim has exactly the owner the model expects, and no other claim is stored.
+	prop_assert_eq!(&proofs, model);
+
+	for claim in proofs.keys() {
+		prop_assert!(claim.len() as u32 <= MaxClaimLength::get());
+	}
+
+	for who in 1..=3u64 {
+		let owned: Vec<&Vec<u8>> = model.iter().filter(|(_, owner)| **owner == who).map(|(claim, _)| claim).collect();
+		let deposits = owned.iter().map(|claim| PoeModule::deposit_for(claim)).sum::<u64>();
+
+		prop_assert_eq!(PoeModule::owned_claims_count(who), owned.len() as u32);
+		prop_assert_eq!(Balances::reserved_balance(who), deposits);
+	}
+
+	Ok(())
+}
+
+proptest! {
+	#[test]
+	fn claim_calls_match_model(ops in prop_vec(op(), 1..50)) {
+		new_test_ext().execute_with(|| {
+			let mut model = BTreeMap::new();
+
+			for op in &ops {
+				let expected = apply(&mut model, op);
+
+				prop_assert_eq!(dispatch(op), expected, "{:?}", op);
+				check_invariants(&model)?;
+			}
+
+			Ok(())
+		})?;
+	}
+}
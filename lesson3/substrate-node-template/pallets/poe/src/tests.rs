@@ -1,5 +1,1299 @@
 // Tests to be written here
 
-use crate::{Error, mock::*};
-use frame_support::{assert_ok, assert_noop};
+use crate::{Error, RawEvent, ClaimInfo, GenesisConfig, HashAlgorithm, Proofs, ProvenanceAction, ProvenanceRecord, Releases, RootInfo, StorageVersion, CLAIMS_PER_PAGE, mock::*};
+use sp_core::H256;
+use sp_io::hashing::blake2_256;
+use sp_runtime::testing::TestSignature;
+use codec::Encode;
+use sp_runtime::traits::BadOrigin;
+use frame_system::RawOrigin;
+use frame_support::{
+	assert_ok, assert_noop, Blake2_128Concat, StorageHasher,
+	storage::migration::{get_storage_value, put_storage_value},
+	traits::{Get, OnInitialize, OnRuntimeUpgrade},
+};
 
+fn last_event() -> TestEvent {
+	System::events().pop().expect("an event was deposited").event
+}
+
+#[test]
+fn create_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_eq!(Proofs::<Test>::get(&claim).owner, 1);
+		assert_eq!(Proofs::<Test>::get(&claim).block_number, 1);
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimCreated(1, claim)));
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_already_exist() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_eq!(Proofs::<Test>::get(&claim).owner, 1);
+	});
+}
+
+#[test]
+fn create_claim_accepts_claim_at_max_length() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MaxClaimLength::get() as usize];
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert!(Proofs::<Test>::contains_key(&claim));
+	});
+}
+
+#[test]
+fn create_claim_failed_when_claim_too_long() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0; MaxClaimLength::get() as usize + 1];
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None),
+			Error::<Test>::ProofTooLong
+		);
+		assert!(!Proofs::<Test>::contains_key(&claim));
+	});
+}
+
+#[test]
+fn revoke_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), claim.clone()));
+
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimRevoked(1, claim)));
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(1), vec![0, 1]),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_failed_when_not_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(2), claim.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_eq!(Proofs::<Test>::get(&claim).owner, 1);
+	});
+}
+
+#[test]
+fn transfer_claim_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		System::set_block_number(2);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), claim.clone(), 2));
+
+		assert_eq!(Proofs::<Test>::get(&claim).owner, 2);
+		assert_eq!(Proofs::<Test>::get(&claim).block_number, 2);
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimTransferred(1, 2, claim)));
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_claim_not_exist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), vec![0, 1], 2),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_not_owner() {
+	new_test_ext().execute_with(|| {
+		let claim = vec![0, 1];
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), claim.clone(), Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(2), claim.clone(), 3),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_eq!(Proofs::<Test>::get(&claim).owner, 1);
+	});
+}
+
+#[test]
+fn operator_actions_name_operator_and_owner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(3), vec![1], 2));
+
+		let events: Vec<_> = System::events().into_iter().map(|r| r.event).collect();
+		assert!(events.contains(&TestEvent::poe(RawEvent::ClaimTransferredByOperator(3, 1, 2, vec![1]))));
+	});
+}
+
+#[test]
+fn create_claim_indexes_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_eq!(PoeModule::owned_claims_count(1), 2);
+		assert_eq!(PoeModule::claims_of(&1, 0), vec![vec![1], vec![2]]);
+		assert_eq!(PoeModule::claims_of(&2, 0), Vec::<Vec<u8>>::new());
+	});
+}
+
+#[test]
+fn revoke_claim_keeps_owner_index_contiguous() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![3], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+
+		assert_eq!(PoeModule::owned_claims_count(1), 2);
+		assert_eq!(PoeModule::claims_of(&1, 0), vec![vec![3], vec![2]]);
+	});
+}
+
+#[test]
+fn transfer_claim_moves_owner_index() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), vec![1], 2));
+
+		assert_eq!(PoeModule::claims_of(&1, 0), Vec::<Vec<u8>>::new());
+		assert_eq!(PoeModule::claims_of(&2, 0), vec![vec![1]]);
+	});
+}
+
+#[test]
+fn claims_of_is_paginated() {
+	new_test_ext().execute_with(|| {
+		for i in 0..(CLAIMS_PER_PAGE + 1) {
+			assert_ok!(PoeModule::create_claim(Origin::signed(1), i.to_le_bytes().to_vec(), Vec::new(), HashAlgorithm::Blake2_256, None));
+		}
+
+		assert_eq!(PoeModule::claims_of(&1, 0).len(), CLAIMS_PER_PAGE as usize);
+		assert_eq!(PoeModule::claims_of(&1, 1), vec![CLAIMS_PER_PAGE.to_le_bytes().to_vec()]);
+		assert_eq!(PoeModule::claims_of(&1, 2), Vec::<Vec<u8>>::new());
+	});
+}
+
+#[test]
+fn create_claim_records_metadata() {
+	new_test_ext().execute_with(|| {
+		Timestamp::set_timestamp(42);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], b"note".to_vec(), HashAlgorithm::Sha2_256, None));
+
+		assert_eq!(PoeModule::proofs(vec![1]), ClaimInfo {
+			owner: 1,
+			block_number: 0,
+			created_at: 42,
+			note: b"note".to_vec(),
+			hash_algorithm: HashAlgorithm::Sha2_256,
+		});
+	});
+}
+
+#[test]
+fn create_claim_failed_when_note_too_long() {
+	new_test_ext().execute_with(|| {
+		let note = vec![0; MaxNoteLength::get() as usize + 1];
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), vec![1], note, HashAlgorithm::Blake2_256, None),
+			Error::<Test>::NoteTooLong
+		);
+	});
+}
+
+#[test]
+fn runtime_upgrade_migrates_tuple_proofs() {
+	new_test_ext().execute_with(|| {
+		put_storage_value(
+			b"TemplateModule",
+			b"Proofs",
+			&Blake2_128Concat::hash(&vec![1u8].encode()),
+			(1u64, 5u64),
+		);
+
+		PoeModule::on_runtime_upgrade();
+
+		assert_eq!(PoeModule::proofs(vec![1]), ClaimInfo {
+			owner: 1,
+			block_number: 5,
+			created_at: 0,
+			note: Vec::new(),
+			hash_algorithm: HashAlgorithm::Blake2_256,
+		});
+	});
+}
+
+#[test]
+fn runtime_upgrade_indexes_legacy_claims() {
+	new_test_ext().execute_with(|| {
+		// Claim 1 predates the owner index, claims 2 and 3 were indexed when created.
+		for claim in &[vec![1u8], vec![2u8], vec![3u8]] {
+			put_storage_value(b"TemplateModule", b"Proofs", &Blake2_128Concat::hash(&claim.encode()), (1u64, 5u64));
+		}
+		for (index, claim) in [vec![2u8], vec![3u8]].iter().enumerate() {
+			let index = index as u32;
+			put_storage_value(b"TemplateModule", b"OwnedClaims", &Blake2_128Concat::hash(&(1u64, index).encode()), claim.clone());
+			put_storage_value(b"TemplateModule", b"OwnedClaimsIndex", &Blake2_128Concat::hash(&claim.encode()), index);
+		}
+		put_storage_value(b"TemplateModule", b"OwnedClaimsCount", &Blake2_128Concat::hash(&1u64.encode()), 2u32);
+
+		PoeModule::on_runtime_upgrade();
+
+		let mut owned = PoeModule::claims_of(&1, 0);
+		owned.sort();
+		assert_eq!(owned, vec![vec![1], vec![2], vec![3]]);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+
+		let mut owned = PoeModule::claims_of(&1, 0);
+		owned.sort();
+		assert_eq!(owned, vec![vec![2], vec![3]]);
+	});
+}
+
+#[test]
+fn buy_claim_pays_asking_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(1), vec![1], Some(10)));
+
+		assert_ok!(PoeModule::buy_claim(Origin::signed(2), vec![1], 15));
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 2);
+		assert_eq!(PoeModule::claim_price(vec![1]), None);
+		assert_eq!(Balances::free_balance(1), 1010);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(2), 987);
+		assert_eq!(Balances::reserved_balance(2), 3);
+	});
+}
+
+#[test]
+fn buy_claim_failed_when_buyer_cannot_afford_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(1), vec![1], Some(998)));
+
+		assert_noop!(
+			PoeModule::buy_claim(Origin::signed(2), vec![1], 998),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn buy_claim_failed_when_not_for_sale() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::buy_claim(Origin::signed(2), vec![1], 10),
+			Error::<Test>::NotForSale
+		);
+	});
+}
+
+#[test]
+fn buy_claim_failed_when_price_too_low() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(1), vec![1], Some(10)));
+
+		assert_noop!(
+			PoeModule::buy_claim(Origin::signed(2), vec![1], 9),
+			Error::<Test>::PriceTooLow
+		);
+	});
+}
+
+#[test]
+fn set_claim_price_failed_when_not_owner() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::set_claim_price(Origin::signed(2), vec![1], Some(10)),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn transfer_and_revoke_clear_price() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(1), vec![1], Some(10)));
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(1), vec![2], Some(10)));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), vec![1], 2));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![2]));
+
+		assert_eq!(PoeModule::claim_price(vec![1]), None);
+		assert_eq!(PoeModule::claim_price(vec![2]), None);
+	});
+}
+
+#[test]
+fn create_claim_reserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1, 2, 3], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_eq!(PoeModule::claim_deposit(vec![1, 2, 3]), 5);
+		assert_eq!(Balances::reserved_balance(1), 5);
+		assert_eq!(Balances::free_balance(1), 995);
+	});
+}
+
+#[test]
+fn create_claim_failed_when_deposit_unaffordable() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(4), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn revoke_claim_unreserves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+
+		assert_eq!(PoeModule::claim_deposit(vec![1]), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1000);
+	});
+}
+
+#[test]
+fn transfer_claim_moves_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), vec![1], 2));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 3);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), vec![1]));
+
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1003);
+	});
+}
+
+#[test]
+fn transfer_claim_failed_when_recipient_has_no_account() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(1), vec![1], 4),
+			pallet_balances::Error::<Test>::DeadAccount
+		);
+	});
+}
+
+#[test]
+fn create_claims_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claims(Origin::signed(1), vec![vec![1], vec![2]]));
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 1);
+		assert_eq!(PoeModule::proofs(vec![2]).owner, 1);
+		assert_eq!(PoeModule::owned_claims_count(1), 2);
+		assert_eq!(Balances::reserved_balance(1), 6);
+	});
+}
+
+#[test]
+fn create_claims_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::create_claims(Origin::signed(1), vec![vec![1], vec![2]]),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_noop!(
+			PoeModule::create_claims(Origin::signed(1), vec![vec![1], vec![1]]),
+			Error::<Test>::DuplicateClaim
+		);
+	});
+}
+
+#[test]
+fn create_claims_failed_when_batch_too_large() {
+	new_test_ext().execute_with(|| {
+		let claims = (0..MaxBatchSize::get() + 1).map(|i| vec![i as u8]).collect();
+
+		assert_noop!(
+			PoeModule::create_claims(Origin::signed(1), claims),
+			Error::<Test>::BatchTooLarge
+		);
+	});
+}
+
+#[test]
+fn revoke_claims_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claims(Origin::signed(1), vec![vec![1], vec![2]]));
+
+		assert_ok!(PoeModule::revoke_claims(Origin::signed(1), vec![vec![1], vec![2]]));
+
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert!(!Proofs::<Test>::contains_key(vec![2]));
+		assert_eq!(PoeModule::owned_claims_count(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn revoke_claims_is_all_or_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::revoke_claims(Origin::signed(1), vec![vec![1], vec![2]]),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn transfer_claims_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claims(Origin::signed(1), vec![vec![1], vec![2]]));
+
+		assert_ok!(PoeModule::transfer_claims(Origin::signed(1), vec![vec![1], vec![2]], 2));
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 2);
+		assert_eq!(PoeModule::proofs(vec![2]).owner, 2);
+		assert_eq!(PoeModule::owned_claims_count(1), 0);
+		assert_eq!(PoeModule::owned_claims_count(2), 2);
+		assert_eq!(Balances::reserved_balance(2), 6);
+	});
+}
+
+#[test]
+fn offered_claim_transfers_on_accept() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::offer_claim(Origin::signed(1), vec![1], 2));
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 1);
+		assert_eq!(PoeModule::pending_transfer(vec![1]), Some(2));
+
+		assert_ok!(PoeModule::accept_claim(Origin::signed(2), vec![1]));
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 2);
+		assert_eq!(PoeModule::pending_transfer(vec![1]), None);
+		// The recipient puts up its own deposit and the previous owner gets theirs back.
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1000);
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(Balances::free_balance(2), 997);
+	});
+}
+
+#[test]
+fn accept_claim_failed_when_not_recipient() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::accept_claim(Origin::signed(2), vec![1]),
+			Error::<Test>::NoPendingTransfer
+		);
+
+		assert_ok!(PoeModule::offer_claim(Origin::signed(1), vec![1], 2));
+
+		assert_noop!(
+			PoeModule::accept_claim(Origin::signed(3), vec![1]),
+			Error::<Test>::NotTransferRecipient
+		);
+	});
+}
+
+#[test]
+fn either_party_can_cancel_claim_offer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::offer_claim(Origin::signed(1), vec![1], 2));
+
+		assert_noop!(
+			PoeModule::cancel_claim_offer(Origin::signed(3), vec![1]),
+			Error::<Test>::NotTransferParty
+		);
+
+		assert_ok!(PoeModule::cancel_claim_offer(Origin::signed(2), vec![1]));
+		assert_eq!(PoeModule::pending_transfer(vec![1]), None);
+
+		assert_ok!(PoeModule::offer_claim(Origin::signed(1), vec![1], 2));
+		assert_ok!(PoeModule::cancel_claim_offer(Origin::signed(1), vec![1]));
+		assert_eq!(PoeModule::pending_transfer(vec![1]), None);
+	});
+}
+
+#[test]
+fn revoke_claim_clears_pending_offer() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::offer_claim(Origin::signed(1), vec![1], 2));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+
+		assert_eq!(PoeModule::pending_transfer(vec![1]), None);
+	});
+}
+
+#[test]
+fn expired_claim_is_removed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(5)));
+
+		assert_eq!(PoeModule::claim_expiry(vec![1]), Some(5));
+		assert_eq!(PoeModule::expiries(5), vec![vec![1]]);
+
+		PoeModule::on_initialize(4);
+		assert!(Proofs::<Test>::contains_key(vec![1]));
+
+		PoeModule::on_initialize(5);
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert_eq!(PoeModule::claim_expiry(vec![1]), None);
+		assert_eq!(PoeModule::expiries(5), Vec::<Vec<u8>>::new());
+		assert_eq!(PoeModule::owned_claims_count(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// The claim can be registered again once it has expired.
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+	});
+}
+
+#[test]
+fn frozen_claim_does_not_expire() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(5)));
+		assert_ok!(PoeModule::freeze_claim(RawOrigin::Root.into(), vec![1], 7));
+
+		PoeModule::on_initialize(5);
+
+		assert!(Proofs::<Test>::contains_key(vec![1]));
+		assert_eq!(PoeModule::frozen_claim(vec![1]), Some(7));
+		assert_eq!(PoeModule::expiries(5), Vec::<Vec<u8>>::new());
+		assert_eq!(Balances::reserved_balance(1), 3);
+	});
+}
+
+#[test]
+fn frozen_claim_expires_once_unfrozen() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(5)));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, Some(9)));
+		assert_ok!(PoeModule::freeze_claim(RawOrigin::Root.into(), vec![1], 7));
+		assert_ok!(PoeModule::freeze_claim(RawOrigin::Root.into(), vec![2], 7));
+
+		System::set_block_number(6);
+		PoeModule::on_initialize(5);
+		assert!(Proofs::<Test>::contains_key(vec![1]));
+
+		assert_ok!(PoeModule::unfreeze_claim(RawOrigin::Root.into(), vec![1]));
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert_eq!(PoeModule::claim_expiry(vec![1]), None);
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimExpired(1, vec![1])));
+
+		// A claim unfrozen before its expiry still expires on schedule.
+		assert_ok!(PoeModule::unfreeze_claim(RawOrigin::Root.into(), vec![2]));
+		assert!(Proofs::<Test>::contains_key(vec![2]));
+
+		PoeModule::on_initialize(9);
+		assert!(!Proofs::<Test>::contains_key(vec![2]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn revoked_claim_is_unscheduled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(5)));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, Some(5)));
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+
+		assert_eq!(PoeModule::claim_expiry(vec![1]), None);
+		assert_eq!(PoeModule::expiries(5), vec![vec![2]]);
+	});
+}
+
+#[test]
+fn create_claim_failed_with_invalid_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(5)),
+			Error::<Test>::ExpiryInPast
+		);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(6)));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, Some(6)));
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(1), vec![3], Vec::new(), HashAlgorithm::Blake2_256, Some(6)),
+			Error::<Test>::TooManyExpiries
+		);
+	});
+}
+
+fn hash_leaf(leaf: &[u8]) -> H256 {
+	H256::from(blake2_256(&[&[0u8][..], leaf].concat()))
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+	let (left, right) = if a <= b { (a, b) } else { (b, a) };
+	H256::from(blake2_256(&[&[1u8][..], left.as_bytes(), right.as_bytes()].concat()))
+}
+
+#[test]
+fn anchor_root_works() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+
+		assert_ok!(PoeModule::anchor_root(Origin::signed(1), root, 4));
+
+		assert_eq!(PoeModule::anchored_root(root), Some(RootInfo {
+			owner: 1,
+			leaf_count: 4,
+			deposit: 5,
+			block_number: 0,
+		}));
+		assert_eq!(Balances::reserved_balance(1), 5);
+
+		assert_noop!(
+			PoeModule::anchor_root(Origin::signed(2), root, 4),
+			Error::<Test>::RootAlreadyAnchored
+		);
+		assert_noop!(
+			PoeModule::anchor_root(Origin::signed(2), H256::repeat_byte(2), 0),
+			Error::<Test>::EmptyRoot
+		);
+	});
+}
+
+#[test]
+fn remove_root_returns_deposit() {
+	new_test_ext().execute_with(|| {
+		let root = H256::repeat_byte(1);
+		assert_ok!(PoeModule::anchor_root(Origin::signed(1), root, 4));
+
+		assert_noop!(PoeModule::remove_root(Origin::signed(2), root), Error::<Test>::NotRootOwner);
+		assert_noop!(
+			PoeModule::remove_root(Origin::signed(1), H256::repeat_byte(2)),
+			Error::<Test>::RootNotAnchored
+		);
+
+		assert_ok!(PoeModule::remove_root(Origin::signed(1), root));
+
+		assert_eq!(PoeModule::anchored_root(root), None);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1000);
+	});
+}
+
+#[test]
+fn verify_inclusion_works() {
+	new_test_ext().execute_with(|| {
+		let leaves: Vec<H256> = (0u8..4).map(|i| hash_leaf(&[i])).collect();
+		let left = hash_pair(leaves[0], leaves[1]);
+		let right = hash_pair(leaves[2], leaves[3]);
+		let root = hash_pair(left, right);
+
+		// Unanchored roots never verify.
+		assert!(!PoeModule::verify_inclusion(root, &[2], &[leaves[3], left]));
+
+		assert_ok!(PoeModule::anchor_root(Origin::signed(1), root, 4));
+
+		assert!(PoeModule::verify_inclusion(root, &[2], &[leaves[3], left]));
+		assert!(PoeModule::verify_inclusion(root, &[0], &[leaves[1], right]));
+		assert!(!PoeModule::verify_inclusion(root, &[4], &[leaves[3], left]));
+		assert!(!PoeModule::verify_inclusion(root, &[2], &[leaves[2], left]));
+	});
+}
+
+#[test]
+fn verify_inclusion_rejects_inner_nodes_and_deep_proofs() {
+	new_test_ext().execute_with(|| {
+		let leaves: Vec<H256> = (0u8..4).map(|i| hash_leaf(&[i])).collect();
+		let left = hash_pair(leaves[0], leaves[1]);
+		let right = hash_pair(leaves[2], leaves[3]);
+		let root = hash_pair(left, right);
+
+		assert_ok!(PoeModule::anchor_root(Origin::signed(1), root, 4));
+
+		// An inner node is not a leaf, even given its sibling.
+		let (first, second) = if left <= right { (left, right) } else { (right, left) };
+		assert!(!PoeModule::verify_inclusion(root, &[first.as_bytes(), second.as_bytes()].concat(), &[]));
+		assert!(!PoeModule::verify_inclusion(root, left.as_bytes(), &[right]));
+
+		// A tree of 2 leaves is only 1 level deep.
+		let small_root = hash_pair(hash_pair(leaves[0], leaves[1]), right);
+		assert_ok!(PoeModule::anchor_root(Origin::signed(1), small_root, 2));
+		assert!(!PoeModule::verify_inclusion(small_root, &[0], &[leaves[1], right]));
+	});
+}
+
+#[test]
+fn runtime_upgrade_moves_storage_to_poe_prefix() {
+	new_test_ext().execute_with(|| {
+		let info = ClaimInfo {
+			owner: 1,
+			block_number: 5,
+			created_at: 42,
+			note: b"note".to_vec(),
+			hash_algorithm: HashAlgorithm::Keccak256,
+		};
+		let claim_key = Blake2_128Concat::hash(&vec![1u8].encode());
+
+		put_storage_value(b"TemplateModule", b"Proofs", &claim_key, info.clone());
+		put_storage_value(b"TemplateModule", b"OwnedClaims", &Blake2_128Concat::hash(&(1u64, 0u32).encode()), vec![1u8]);
+		put_storage_value(b"TemplateModule", b"OwnedClaimsCount", &Blake2_128Concat::hash(&1u64.encode()), 1u32);
+		put_storage_value(b"TemplateModule", b"OwnedClaimsIndex", &claim_key, 0u32);
+		put_storage_value(b"TemplateModule", b"StorageVersion", &[], Releases::V2);
+		// Belongs to the template pallet, which still uses the `TemplateModule` prefix.
+		put_storage_value(b"TemplateModule", b"Something", &[], 7u32);
+
+		PoeModule::on_runtime_upgrade();
+
+		assert_eq!(PoeModule::proofs(vec![1]), info);
+		assert_eq!(PoeModule::claims_of(&1, 0), vec![vec![1]]);
+		assert_eq!(StorageVersion::get(), Releases::V3);
+		assert_eq!(get_storage_value::<ClaimInfo<u64, u64, u64>>(b"TemplateModule", b"Proofs", &claim_key), None);
+		assert_eq!(get_storage_value::<Releases>(b"TemplateModule", b"StorageVersion", &[]), None);
+		assert_eq!(get_storage_value::<u32>(b"TemplateModule", b"Something", &[]), Some(7));
+
+		// Migrated claims behave like any other.
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+		assert_eq!(PoeModule::claims_of(&1, 0), Vec::<Vec<u8>>::new());
+	});
+}
+
+#[test]
+fn runtime_upgrade_is_noop_on_current_storage() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::put(Releases::V3);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_eq!(PoeModule::on_runtime_upgrade(), 0);
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 1);
+		assert_eq!(StorageVersion::get(), Releases::V3);
+	});
+}
+
+#[test]
+fn cosigned_claim_is_finalized_at_threshold() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![1, 2, 3], 2));
+
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert_eq!(Balances::reserved_balance(1), 3);
+
+		assert_ok!(PoeModule::endorse_claim(Origin::signed(2), vec![1]));
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert_eq!(PoeModule::cosigned_claim(vec![1]).unwrap().endorsements, vec![2]);
+
+		assert_ok!(PoeModule::endorse_claim(Origin::signed(3), vec![1]));
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 1);
+		assert_eq!(PoeModule::claim_deposit(vec![1]), 3);
+
+		let cosigned = PoeModule::cosigned_claim(vec![1]).unwrap();
+		assert!(cosigned.finalized);
+		assert_eq!(cosigned.endorsements, vec![2, 3]);
+
+		assert_noop!(
+			PoeModule::endorse_claim(Origin::signed(1), vec![1]),
+			Error::<Test>::AlreadyFinalized
+		);
+	});
+}
+
+#[test]
+fn endorse_claim_failed_with_invalid_signer() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::endorse_claim(Origin::signed(2), vec![1]),
+			Error::<Test>::NotCosignedClaim
+		);
+
+		assert_ok!(PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![1, 2], 2));
+
+		assert_noop!(
+			PoeModule::endorse_claim(Origin::signed(3), vec![1]),
+			Error::<Test>::NotSigner
+		);
+
+		assert_ok!(PoeModule::endorse_claim(Origin::signed(2), vec![1]));
+
+		assert_noop!(
+			PoeModule::endorse_claim(Origin::signed(2), vec![1]),
+			Error::<Test>::AlreadyEndorsed
+		);
+	});
+}
+
+#[test]
+fn open_cosigned_claim_failed_with_invalid_signers() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![1, 2], 3),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![1, 2], 0),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![1, 2, 2], 2),
+			Error::<Test>::DuplicateSigner
+		);
+		assert_noop!(
+			PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![1, 2, 3, 4], 2),
+			Error::<Test>::TooManySigners
+		);
+	});
+}
+
+#[test]
+fn pending_cosigned_claim_blocks_create_claim() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::open_cosigned_claim(Origin::signed(1), vec![1], vec![2], 1));
+
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None),
+			Error::<Test>::ProofAlreadyExist
+		);
+
+		assert_ok!(PoeModule::cancel_cosigned_claim(Origin::signed(1), vec![1]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+	});
+}
+
+#[test]
+fn create_claim_for_works() {
+	new_test_ext().execute_with(|| {
+		let signature = TestSignature(4, PoeModule::create_claim_for_payload(&[1], 0));
+
+		assert_ok!(PoeModule::create_claim_for(Origin::signed(1), 4, vec![1], signature, 0));
+
+		assert_eq!(PoeModule::proofs(vec![1]).owner, 4);
+		assert_eq!(PoeModule::claim_nonce(4), 1);
+		assert_eq!(PoeModule::claim_sponsor(vec![1]), Some(1));
+		assert_eq!(Balances::reserved_balance(1), 3);
+
+		// The sponsor gets the deposit back when the owner revokes the claim.
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(4), vec![1]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(PoeModule::claim_sponsor(vec![1]), None);
+	});
+}
+
+#[test]
+fn create_claim_for_rejects_replayed_nonce() {
+	new_test_ext().execute_with(|| {
+		let signature = TestSignature(4, PoeModule::create_claim_for_payload(&[1], 0));
+
+		assert_ok!(PoeModule::create_claim_for(Origin::signed(1), 4, vec![1], signature.clone(), 0));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(4), vec![1]));
+
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::signed(1), 4, vec![1], signature, 0),
+			Error::<Test>::InvalidNonce
+		);
+	});
+}
+
+#[test]
+fn create_claim_for_rejects_bad_signature() {
+	new_test_ext().execute_with(|| {
+		let wrong_signer = TestSignature(5, PoeModule::create_claim_for_payload(&[1], 0));
+		let wrong_claim = TestSignature(4, PoeModule::create_claim_for_payload(&[2], 0));
+
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::signed(1), 4, vec![1], wrong_signer, 0),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::signed(1), 4, vec![1], wrong_claim, 0),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn create_claim_for_rejects_signature_from_another_chain() {
+	new_test_ext().execute_with(|| {
+		let signature = TestSignature(4, PoeModule::create_claim_for_payload(&[1], 0));
+
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+
+		assert_noop!(
+			PoeModule::create_claim_for(Origin::signed(1), 4, vec![1], signature, 0),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn claim_history_survives_revoke_and_recreate() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		System::set_block_number(2);
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(1), vec![1], 2));
+
+		System::set_block_number(3);
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(2), vec![1]));
+
+		assert_eq!(PoeModule::claim_history(vec![1]), vec![
+			ProvenanceRecord { action: ProvenanceAction::Created, actor: Some(1), owner: 1, block_number: 1 },
+			ProvenanceRecord { action: ProvenanceAction::Transferred, actor: Some(1), owner: 2, block_number: 2 },
+			ProvenanceRecord { action: ProvenanceAction::Revoked, actor: Some(2), owner: 2, block_number: 3 },
+		]);
+
+		// Re-creating the claim appends to its history, dropping the oldest record past the bound.
+		System::set_block_number(4);
+		assert_ok!(PoeModule::create_claim(Origin::signed(3), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_eq!(PoeModule::claim_history(vec![1]), vec![
+			ProvenanceRecord { action: ProvenanceAction::Transferred, actor: Some(1), owner: 2, block_number: 2 },
+			ProvenanceRecord { action: ProvenanceAction::Revoked, actor: Some(2), owner: 2, block_number: 3 },
+			ProvenanceRecord { action: ProvenanceAction::Created, actor: Some(3), owner: 3, block_number: 4 },
+		]);
+	});
+}
+
+#[test]
+fn claim_history_records_sale_and_expiry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, Some(5)));
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(1), vec![1], Some(10)));
+		assert_ok!(PoeModule::buy_claim(Origin::signed(2), vec![1], 10));
+
+		System::set_block_number(5);
+		PoeModule::on_initialize(5);
+
+		assert_eq!(PoeModule::claim_history(vec![1]), vec![
+			ProvenanceRecord { action: ProvenanceAction::Created, actor: Some(1), owner: 1, block_number: 1 },
+			ProvenanceRecord { action: ProvenanceAction::Transferred, actor: Some(2), owner: 2, block_number: 1 },
+			ProvenanceRecord { action: ProvenanceAction::Expired, actor: None, owner: 2, block_number: 5 },
+		]);
+	});
+}
+
+#[test]
+fn operator_can_revoke_and_transfer_claims() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::transfer_claim(Origin::signed(3), vec![1], 2),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+		assert!(PoeModule::is_operator(1, 3));
+
+		assert_ok!(PoeModule::transfer_claim(Origin::signed(3), vec![1], 2));
+		assert_eq!(Proofs::<Test>::get(vec![1]).owner, 2);
+		assert_eq!(Balances::reserved_balance(2), 3);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(3), vec![2]));
+		assert!(!Proofs::<Test>::contains_key(vec![2]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(3), 0);
+
+		// Approval is per owner, so the operator cannot touch the claim now held by 2.
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(3), vec![1]),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn revoke_operator_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::revoke_operator(Origin::signed(1), 3),
+			Error::<Test>::NotOperator
+		);
+
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+		assert_ok!(PoeModule::revoke_operator(Origin::signed(1), 3));
+		assert!(!PoeModule::is_operator(1, 3));
+
+		assert_noop!(
+			PoeModule::revoke_claim(Origin::signed(3), vec![1]),
+			Error::<Test>::NotClaimOwner
+		);
+	});
+}
+
+#[test]
+fn operator_can_revoke_and_transfer_claim_batches() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![2], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::create_claim(Origin::signed(2), vec![3], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::transfer_claims(Origin::signed(3), vec![vec![1], vec![2]], 2),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+
+		// The whole batch fails if the sender may not manage one of the claims.
+		assert_noop!(
+			PoeModule::revoke_claims(Origin::signed(3), vec![vec![1], vec![3]]),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::transfer_claims(Origin::signed(3), vec![vec![1]], 2));
+		assert_eq!(Proofs::<Test>::get(vec![1]).owner, 2);
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_eq!(Balances::reserved_balance(2), 6);
+
+		assert_ok!(PoeModule::revoke_claims(Origin::signed(3), vec![vec![2]]));
+		assert!(!Proofs::<Test>::contains_key(vec![2]));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(3), 0);
+	});
+}
+
+#[test]
+fn operator_can_offer_and_price_claims() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(
+			PoeModule::offer_claim(Origin::signed(3), vec![1], 2),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::set_claim_price(Origin::signed(3), vec![1], Some(10)),
+			Error::<Test>::NotClaimOwner
+		);
+
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+
+		assert_ok!(PoeModule::offer_claim(Origin::signed(3), vec![1], 2));
+		assert_eq!(PoeModule::pending_transfer(vec![1]), Some(2));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimOffered(1, 2, vec![1])));
+
+		assert_ok!(PoeModule::set_claim_price(Origin::signed(3), vec![1], Some(10)));
+		assert_eq!(PoeModule::claim_price(vec![1]), Some(10));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimPriced(1, vec![1], Some(10))));
+	});
+}
+
+#[test]
+fn approve_operator_is_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 2));
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+		assert_eq!(PoeModule::operator_count(1), 2);
+
+		// Approving an operator again does not take another slot.
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 3));
+		assert_eq!(PoeModule::operator_count(1), 2);
+
+		assert_noop!(
+			PoeModule::approve_operator(Origin::signed(1), 4),
+			Error::<Test>::TooManyOperators
+		);
+
+		assert_ok!(PoeModule::revoke_operator(Origin::signed(1), 2));
+		assert_eq!(PoeModule::operator_count(1), 1);
+		assert_ok!(PoeModule::approve_operator(Origin::signed(1), 4));
+	});
+}
+
+#[test]
+fn frozen_claim_rejects_owner_actions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+
+		assert_noop!(PoeModule::freeze_claim(Origin::signed(1), vec![1], 7), BadOrigin);
+		assert_noop!(PoeModule::freeze_claim(RawOrigin::Root.into(), vec![2], 7), Error::<Test>::ClaimNotExist);
+
+		assert_ok!(PoeModule::freeze_claim(RawOrigin::Root.into(), vec![1], 7));
+		assert_eq!(PoeModule::frozen_claim(vec![1]), Some(7));
+
+		assert_noop!(PoeModule::revoke_claim(Origin::signed(1), vec![1]), Error::<Test>::ClaimFrozen);
+		assert_noop!(PoeModule::transfer_claim(Origin::signed(1), vec![1], 2), Error::<Test>::ClaimFrozen);
+		assert_noop!(PoeModule::offer_claim(Origin::signed(1), vec![1], 2), Error::<Test>::ClaimFrozen);
+		assert_noop!(PoeModule::set_claim_price(Origin::signed(1), vec![1], Some(10)), Error::<Test>::ClaimFrozen);
+		assert_noop!(PoeModule::revoke_claims(Origin::signed(1), vec![vec![1]]), Error::<Test>::ClaimFrozen);
+		assert_noop!(PoeModule::transfer_claims(Origin::signed(1), vec![vec![1]], 2), Error::<Test>::ClaimFrozen);
+
+		assert_noop!(PoeModule::unfreeze_claim(RawOrigin::Root.into(), vec![2]), Error::<Test>::ClaimNotFrozen);
+		assert_ok!(PoeModule::unfreeze_claim(RawOrigin::Root.into(), vec![1]));
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+	});
+}
+
+#[test]
+fn force_transfer_and_revoke_claim_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(Origin::signed(1), vec![1], Vec::new(), HashAlgorithm::Blake2_256, None));
+		assert_ok!(PoeModule::freeze_claim(RawOrigin::Root.into(), vec![1], 7));
+
+		assert_noop!(PoeModule::force_transfer_claim(Origin::signed(2), vec![1], 2, 7), BadOrigin);
+		assert_ok!(PoeModule::force_transfer_claim(RawOrigin::Root.into(), vec![1], 2, 7));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimForceTransferred(1, 2, vec![1], 7)));
+		assert_eq!(Proofs::<Test>::get(vec![1]).owner, 2);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(PoeModule::frozen_claim(vec![1]), Some(7));
+
+		assert_noop!(PoeModule::force_revoke_claim(Origin::signed(2), vec![1], 8), BadOrigin);
+		assert_ok!(PoeModule::force_revoke_claim(RawOrigin::Root.into(), vec![1], 8));
+		assert_eq!(last_event(), TestEvent::poe(RawEvent::ClaimForceRevoked(2, vec![1], 8)));
+		assert!(!Proofs::<Test>::contains_key(vec![1]));
+		assert_eq!(PoeModule::frozen_claim(vec![1]), None);
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		assert_eq!(PoeModule::claim_history(vec![1]).last(), Some(&ProvenanceRecord {
+			action: ProvenanceAction::Revoked,
+			actor: None,
+			owner: 2,
+			block_number: 1,
+		}));
+	});
+}
+
+fn genesis_ext(claims: Vec<(Vec<u8>, u64)>) -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	GenesisConfig::<Test> { claims }.assimilate_storage(&mut t).unwrap();
+	t.into()
+}
+
+#[test]
+fn genesis_claims_are_recorded() {
+	genesis_ext(vec![(vec![1], 1), (vec![2], 2)]).execute_with(|| {
+		assert_eq!(Proofs::<Test>::get(vec![1]).owner, 1);
+		assert_eq!(Proofs::<Test>::get(vec![2]).owner, 2);
+		assert_eq!(PoeModule::claims_of(&1, 0), vec![vec![1]]);
+		assert_eq!(PoeModule::claim_deposit(vec![1]), 0);
+
+		assert_ok!(PoeModule::revoke_claim(Origin::signed(1), vec![1]));
+	});
+}
+
+#[test]
+#[should_panic(expected = "Genesis claim is longer than MaxClaimLength")]
+fn genesis_rejects_long_claim() {
+	let claim = vec![0; MaxClaimLength::get() as usize + 1];
+	genesis_ext(vec![(claim, 1)]);
+}
+
+#[test]
+fn content_hash_algorithm_is_derived_from_content_hashing() {
+	assert_eq!(PoeModule::content_hash_algorithm(), Some(HashAlgorithm::Blake2_256));
+
+	let blake2 = HashAlgorithm::Blake2_256.hash(b"poe");
+	assert_eq!(blake2, blake2_256(b"poe"));
+	assert_ne!(HashAlgorithm::Sha2_256.hash(b"poe"), blake2);
+	assert_ne!(HashAlgorithm::Keccak256.hash(b"poe"), blake2);
+}
+
+#[test]
+fn create_claim_from_content_stores_digest() {
+	new_test_ext().execute_with(|| {
+		let content = b"hello world".to_vec();
+		let digest = blake2_256(&content).to_vec();
+
+		assert_ok!(PoeModule::create_claim_from_content(Origin::signed(1), content.clone()));
+
+		assert_eq!(Proofs::<Test>::get(&digest).owner, 1);
+		assert_eq!(Proofs::<Test>::get(&digest).hash_algorithm, HashAlgorithm::Blake2_256);
+		assert_eq!(PoeModule::claims_of(&1, 0), vec![digest.clone()]);
+
+		// The digest is an ordinary claim, so registering it again either way fails.
+		assert_noop!(
+			PoeModule::create_claim_from_content(Origin::signed(2), content),
+			Error::<Test>::ProofAlreadyExist
+		);
+		assert_noop!(
+			PoeModule::create_claim(Origin::signed(2), digest, Vec::new(), HashAlgorithm::Blake2_256, None),
+			Error::<Test>::ProofAlreadyExist
+		);
+	});
+}
+
+#[test]
+fn create_claim_from_content_failed_when_content_too_long() {
+	new_test_ext().execute_with(|| {
+		let content = vec![0; MaxContentLength::get() as usize];
+		assert_ok!(PoeModule::create_claim_from_content(Origin::signed(1), content));
+
+		let content = vec![0; MaxContentLength::get() as usize + 1];
+		assert_noop!(
+			PoeModule::create_claim_from_content(Origin::signed(1), content),
+			Error::<Test>::ContentTooLong
+		);
+	});
+}
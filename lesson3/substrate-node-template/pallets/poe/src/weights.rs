@@ -0,0 +1,234 @@
+//! Weights for pallet_poe.
+//!
+//! Each call is charged a base execution time, a component for the claim length `l`, the
+//! content length `c` or the number of co-signers `s`, and its worst-case database reads
+//! and writes.
+//!
+//! The execution times are estimates that have not been measured yet. Regenerate them on
+//! reference hardware with a node built with `--features runtime-benchmarks`:
+//!
+//! ```text
+//! ./target/release/node-template benchmark --chain dev --execution wasm \
+//!     --wasm-execution compiled --pallet poe --extrinsic '*' --steps 50 --repeat 20
+//! ```
+//!
+//! The reads and writes are counted by hand from the storage each call touches, taking the
+//! branch with an operator sender, an expiry and a swapped owner index where one exists.
+
+#![allow(unused_parens)]
+
+use frame_support::weights::{Weight, constants::RocksDbWeight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_poe.
+pub trait WeightInfo {
+	fn create_claim(l: u32) -> Weight;
+	fn create_claim_from_content(c: u32) -> Weight;
+	fn create_claim_for(l: u32) -> Weight;
+	fn revoke_claim(l: u32) -> Weight;
+	fn transfer_claim(l: u32) -> Weight;
+	fn offer_claim(l: u32) -> Weight;
+	fn accept_claim(l: u32) -> Weight;
+	fn cancel_claim_offer(l: u32) -> Weight;
+	fn set_claim_price(l: u32) -> Weight;
+	fn buy_claim(l: u32) -> Weight;
+	fn open_cosigned_claim(s: u32) -> Weight;
+	fn endorse_claim(s: u32) -> Weight;
+	fn anchor_root() -> Weight;
+	fn remove_root() -> Weight;
+	fn approve_operator() -> Weight;
+	fn revoke_operator() -> Weight;
+}
+
+/// Weights for pallet_poe using the node's configured database weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+	fn create_claim(l: u32) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((12_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn create_claim_from_content(c: u32) -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn create_claim_for(l: u32) -> Weight {
+		(112_000_000 as Weight)
+			.saturating_add((12_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn revoke_claim(l: u32) -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add((18_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	fn transfer_claim(l: u32) -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add((20_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	fn offer_claim(l: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn accept_claim(l: u32) -> Weight {
+		(82_000_000 as Weight)
+			.saturating_add((20_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+	}
+	fn cancel_claim_offer(l: u32) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_claim_price(l: u32) -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn buy_claim(l: u32) -> Weight {
+		(104_000_000 as Weight)
+			.saturating_add((20_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+	}
+	fn open_cosigned_claim(s: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((1_100_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn endorse_claim(s: u32) -> Weight {
+		(56_000_000 as Weight)
+			.saturating_add((900_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(7 as Weight))
+	}
+	fn anchor_root() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn remove_root() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn approve_operator() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn revoke_operator() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_claim(l: u32) -> Weight {
+		(58_000_000 as Weight)
+			.saturating_add((12_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn create_claim_from_content(c: u32) -> Weight {
+		(63_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn create_claim_for(l: u32) -> Weight {
+		(112_000_000 as Weight)
+			.saturating_add((12_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn revoke_claim(l: u32) -> Weight {
+		(61_000_000 as Weight)
+			.saturating_add((18_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn transfer_claim(l: u32) -> Weight {
+		(74_000_000 as Weight)
+			.saturating_add((20_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn offer_claim(l: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accept_claim(l: u32) -> Weight {
+		(82_000_000 as Weight)
+			.saturating_add((20_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+	}
+	fn cancel_claim_offer(l: u32) -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add((1_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_claim_price(l: u32) -> Weight {
+		(33_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn buy_claim(l: u32) -> Weight {
+		(104_000_000 as Weight)
+			.saturating_add((20_000 as Weight).saturating_mul(l as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+	}
+	fn open_cosigned_claim(s: u32) -> Weight {
+		(47_000_000 as Weight)
+			.saturating_add((1_100_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn endorse_claim(s: u32) -> Weight {
+		(56_000_000 as Weight)
+			.saturating_add((900_000 as Weight).saturating_mul(s as Weight))
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
+	}
+	fn anchor_root() -> Weight {
+		(41_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_root() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn approve_operator() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn revoke_operator() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+}
@@ -66,6 +66,9 @@ pub type Index = u32;
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// Digest item type.
 pub type DigestItem = generic::DigestItem<Hash>;
 
@@ -216,7 +219,7 @@ parameter_types! {
 
 impl timestamp::Trait for Runtime {
 	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
 }
@@ -259,7 +262,18 @@ impl template::Trait for Runtime {
 
 // 附加题答案
 parameter_types! {
-	pub const MaxClaimLength: u32 = 6;
+	// Long enough for a 256-bit digest, such as those of `create_claim_from_content`.
+	pub const MaxClaimLength: u32 = 32;
+	pub const MaxNoteLength: u32 = 256;
+	pub const MaxContentLength: u32 = 4 * 1024;
+	pub const ClaimDeposit: Balance = 100;
+	pub const ClaimDepositPerByte: Balance = 10;
+	pub const RootDeposit: Balance = 100;
+	pub const MaxBatchSize: u32 = 100;
+	pub const MaxExpiriesPerBlock: u32 = 100;
+	pub const MaxSigners: u32 = 16;
+	pub const MaxHistoryLength: u32 = 32;
+	pub const MaxOperators: u32 = 16;
 }
 
 impl poe::Trait for Runtime {
@@ -267,6 +281,37 @@ impl poe::Trait for Runtime {
 	
 	// 附加题答案
 	type MaxClaimLength = MaxClaimLength;
+	type MaxNoteLength = MaxNoteLength;
+	type ContentHashing = BlakeTwo256;
+	type MaxContentLength = MaxContentLength;
+	type Currency = Balances;
+	type ClaimDeposit = ClaimDeposit;
+	type ClaimDepositPerByte = ClaimDepositPerByte;
+	type RootDeposit = RootDeposit;
+	type MaxBatchSize = MaxBatchSize;
+	type MaxExpiriesPerBlock = MaxExpiriesPerBlock;
+	type MaxSigners = MaxSigners;
+	type MaxHistoryLength = MaxHistoryLength;
+	type MaxOperators = MaxOperators;
+	type Signature = Signature;
+	type Signer = <Signature as Verify>::Signer;
+	type WeightInfo = poe::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkSigner = PoeBenchmarkSigner;
+}
+
+/// Signs with a fresh sr25519 key from the keystore of the benchmarking host.
+#[cfg(feature = "runtime-benchmarks")]
+pub struct PoeBenchmarkSigner;
+#[cfg(feature = "runtime-benchmarks")]
+impl poe::BenchmarkSigner<AccountId, Signature> for PoeBenchmarkSigner {
+	fn sign(payload: &[u8]) -> (AccountId, Signature) {
+		let key_type = KeyTypeId(*b"poeb");
+		let public = sp_io::crypto::sr25519_generate(key_type, None);
+		let signature = sp_io::crypto::sr25519_sign(key_type, &public, payload)
+			.expect("the key was just generated in the keystore; qed");
+		(<Signature as Verify>::Signer::from(public).into_account(), signature.into())
+	}
 }
 
 construct_runtime!(
@@ -285,7 +330,7 @@ construct_runtime!(
 		Sudo: sudo::{Module, Call, Config<T>, Storage, Event<T>},
 		// Used for the module template in `./template.rs`
 		TemplateModule: template::{Module, Call, Storage, Event<T>},
-		PoeModule: poe::{Module, Call, Storage, Event<T>},
+		PoeModule: poe::{Module, Call, Storage, Event<T>, Config<T>},
 	}
 );
 
@@ -399,6 +444,42 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl poe_rpc_runtime_api::PoeApi<Block, AccountId, BlockNumber, Moment> for Runtime {
+		fn claim_of(claim: Vec<u8>) -> Option<poe::ClaimInfo<AccountId, BlockNumber, Moment>> {
+			PoeModule::claim_of(&claim)
+		}
+
+		fn claims_of(account: AccountId, page: u32) -> Vec<Vec<u8>> {
+			PoeModule::claims_of(&account, page)
+		}
+
+		fn verify_inclusion(root: Hash, leaf: Vec<u8>, proof: Vec<Hash>) -> bool {
+			PoeModule::verify_inclusion(root, &leaf, &proof)
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	impl frame_benchmarking::Benchmark<Block> for Runtime {
+		fn dispatch_benchmark(
+			pallet: Vec<u8>,
+			benchmark: Vec<u8>,
+			lowest_range_values: Vec<u32>,
+			highest_range_values: Vec<u32>,
+			steps: Vec<u32>,
+			repeat: u32,
+		) -> Result<Vec<frame_benchmarking::BenchmarkBatch>, sp_runtime::RuntimeString> {
+			use frame_benchmarking::{Benchmarking, BenchmarkBatch, add_benchmark};
+
+			let mut batches = Vec::<BenchmarkBatch>::new();
+			let params = (&pallet, &benchmark, &lowest_range_values, &highest_range_values, &steps, repeat);
+
+			add_benchmark!(params, batches, b"poe", PoeModule);
+
+			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
+			Ok(batches)
+		}
+	}
+
 	impl fg_primitives::GrandpaApi<Block> for Runtime {
 		fn grandpa_authorities() -> GrandpaAuthorityList {
 			Grandpa::grandpa_authorities()
@@ -3,11 +3,13 @@
 use codec::{Encode, Decode};
 use frame_support::{
 	decl_module, decl_storage, decl_error, decl_event, ensure, StorageValue, StorageMap, Parameter,
-	traits::{Randomness, Currency, ExistenceRequirement},
+	storage::IterableStorageDoubleMap,
+	traits::{Get, Randomness, Currency, ReservableCurrency, ExistenceRequirement, Imbalance, WithdrawReason},
+	weights::Weight,
 };
 use sp_io::hashing::blake2_128;
 use frame_system::{self as system, ensure_signed};
-use sp_runtime::{DispatchError, traits::{AtLeast32Bit, Bounded, Member}};
+use sp_runtime::{DispatchError, Perbill, RuntimeDebug, traits::{AtLeast32Bit, Bounded, Member, Saturating, Zero}};
 use crate::linked_item::{LinkedList, LinkedItem};
 
 mod linked_item;
@@ -15,14 +17,78 @@ mod linked_item;
 #[derive(Encode, Decode)]
 pub struct Kitty(pub [u8; 16]);
 
+/// Where a kitty comes from.
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
+#[derive(Encode, Decode, Clone)]
+pub struct Genealogy<KittyIndex, BlockNumber> {
+	/// The parents of the kitty. None for kitties created from scratch.
+	pub parents: Option<(KittyIndex, KittyIndex)>,
+	/// 0 for kitties created from scratch, otherwise one more than the higher of its parents' generations.
+	pub generation: u32,
+	/// The block at which the kitty was created or bred.
+	pub birth_block: BlockNumber,
+}
+
+/// How the price of an auction is discovered.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum AuctionKind {
+	/// Bidders outbid each other, and the highest bid wins when the auction ends.
+	English,
+	/// The price falls from the start price to the end price, and the first bid wins.
+	Dutch,
+}
+
+/// A running auction of a kitty.
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
+#[derive(Encode, Decode, Clone)]
+pub struct Auction<AccountId, Balance, BlockNumber> {
+	pub seller: AccountId,
+	pub kind: AuctionKind,
+	/// The minimum bid of an English auction, or the opening price of a Dutch auction.
+	pub start_price: Balance,
+	/// The closing price of a Dutch auction. Unused by English auctions.
+	pub end_price: Balance,
+	pub start_block: BlockNumber,
+	/// The last block in which bids are accepted. The auction is settled at the end of it.
+	pub end_block: BlockNumber,
+	/// The highest bid of an English auction so far, reserved from the bidder.
+	pub highest_bid: Option<(AccountId, Balance)>,
+}
+
+/// An offer to buy a kitty, reserved from the bidder.
+#[cfg_attr(feature = "std", derive(Debug, PartialEq, Eq))]
+#[derive(Encode, Decode, Clone)]
+pub struct Offer<Balance, BlockNumber> {
+	pub amount: Balance,
+	/// The last block in which the offer can be accepted.
+	pub expiry: BlockNumber,
+}
+
 pub trait Trait: frame_system::Trait {
 	type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 	type KittyIndex: Parameter + Member + AtLeast32Bit + Bounded + Default + Copy;
-	type Currency: Currency<Self::AccountId>;
+	type Currency: ReservableCurrency<Self::AccountId>;
 	type Randomness: Randomness<Self::Hash>;
+	/// The number of blocks a generation 0 kitty must wait between breedings.
+	/// Each generation waits this many blocks longer than the previous one.
+	type BreedCooldown: Get<Self::BlockNumber>;
+	/// The share of each sale paid to `Treasury`.
+	type MarketplaceFee: Get<Perbill>;
+	/// The share of each sale paid to the account that created or bred the kitty.
+	type BreederRoyalty: Get<Perbill>;
+	/// The account collecting marketplace fees.
+	type Treasury: Get<Self::AccountId>;
+	/// The maximum number of auctions that may end in the same block.
+	type MaxAuctionsPerBlock: Get<u32>;
+	/// The smallest amount that may be offered for a kitty.
+	type MinimumOffer: Get<BalanceOf<Self>>;
+	/// The maximum number of open offers on a kitty.
+	type MaxOffersPerKitty: Get<u32>;
 }
 
 type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
+type NegativeImbalanceOf<T> = <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::NegativeImbalance;
+type AuctionOf<T> = Auction<<T as frame_system::Trait>::AccountId, BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
 type KittyLinkedItem<T> = LinkedItem<<T as Trait>::KittyIndex>;
 type OwnedKittiesList<T> = LinkedList<OwnedKitties<T>, <T as system::Trait>::AccountId, <T as Trait>::KittyIndex>;
 
@@ -40,6 +106,27 @@ decl_storage! {
 
 		/// Get kitty price. None means not for sale.
 		pub KittyPrices get(fn kitty_price): map hasher(blake2_128_concat) T::KittyIndex => Option<BalanceOf<T>>;
+
+		/// Get kitty siring fee. None means not available for siring.
+		pub SirePrices get(fn sire_price): map hasher(blake2_128_concat) T::KittyIndex => Option<BalanceOf<T>>;
+
+		/// Running auction of each kitty. A kitty in auction cannot be transferred or listed.
+		pub Auctions get(fn auction): map hasher(blake2_128_concat) T::KittyIndex => Option<AuctionOf<T>>;
+		/// Kitties whose auctions are settled at the end of each block.
+		pub AuctionEnds get(fn auction_ends): map hasher(blake2_128_concat) T::BlockNumber => Vec<T::KittyIndex>;
+
+		/// Offers to buy each kitty, keyed by kitty and bidder.
+		pub Offers get(fn offer): double_map hasher(blake2_128_concat) T::KittyIndex, hasher(blake2_128_concat) T::AccountId => Option<Offer<BalanceOf<T>, T::BlockNumber>>;
+		/// The number of open offers on each kitty.
+		pub OfferCounts get(fn offer_count): map hasher(blake2_128_concat) T::KittyIndex => u32;
+
+		/// The account that created or bred each kitty, paid a royalty when it is sold.
+		pub KittyBreeders get(fn kitty_breeder): map hasher(blake2_128_concat) T::KittyIndex => Option<T::AccountId>;
+
+		/// Parents, generation and birth block of each kitty.
+		pub KittyGenealogies get(fn kitty_genealogy): map hasher(blake2_128_concat) T::KittyIndex => Option<Genealogy<T::KittyIndex, T::BlockNumber>>;
+		/// The block from which each kitty can breed again.
+		pub BreedingReadyAt get(fn breeding_ready_at): map hasher(blake2_128_concat) T::KittyIndex => T::BlockNumber;
 	}
 }
 
@@ -51,12 +138,29 @@ decl_error! {
 		RequireOwner,
 		NotForSale,
 		PriceTooLow,
+		BreedingCooldown,
+		NotForSiring,
+		KittyInAuction,
+		NotInAuction,
+		InvalidAuction,
+		AuctionEnded,
+		BidTooLow,
+		BidOnOwnAuction,
+		OfferOnOwnKitty,
+		OfferNotFound,
+		OfferExpired,
+		TooManyAuctions,
+		OfferTooLow,
+		TooManyOffers,
+		OfferNotExpired,
+		SirePriceTooHigh,
 	}
 }
 
 decl_event!(
 	pub enum Event<T> where
 		<T as frame_system::Trait>::AccountId,
+		<T as frame_system::Trait>::BlockNumber,
 		<T as Trait>::KittyIndex,
 		Balance = BalanceOf<T>,
 	{
@@ -66,8 +170,25 @@ decl_event!(
 		Transferred(AccountId, AccountId, KittyIndex),
 		/// A kitty is available for sale. (owner, kitty_id, price)
 		Ask(AccountId, KittyIndex, Option<Balance>),
-		/// A kitty is sold. (from, to, kitty_id, price)
-		Sold(AccountId, AccountId, KittyIndex, Balance),
+		/// A kitty is sold. (from, to, kitty_id, price, marketplace fee, breeder royalty, seller proceeds)
+		/// The seller receives the price less whatever fee and royalty were actually paid.
+		Sold(AccountId, AccountId, KittyIndex, Balance, Balance, Balance, Balance),
+		/// A kitty is available for siring. (owner, kitty_id, fee)
+		SireAsk(AccountId, KittyIndex, Option<Balance>),
+		/// A kitty is bred with another owner's sire. (breeder, sire owner, sire_id, new kitty_id, fee)
+		Sired(AccountId, AccountId, KittyIndex, KittyIndex, Balance),
+		/// A kitty is put up for auction. (seller, kitty_id, end_block)
+		AuctionCreated(AccountId, KittyIndex, BlockNumber),
+		/// A bid is placed in an English auction. (bidder, kitty_id, amount)
+		Bid(AccountId, KittyIndex, Balance),
+		/// An auction ended without a winner. (seller, kitty_id)
+		AuctionExpired(AccountId, KittyIndex),
+		/// An offer is made to buy a kitty. (bidder, kitty_id, amount, expiry)
+		OfferMade(AccountId, KittyIndex, Balance, BlockNumber),
+		/// An offer is withdrawn by its bidder. (bidder, kitty_id)
+		OfferWithdrawn(AccountId, KittyIndex),
+		/// An expired offer is removed and its funds released. (bidder, kitty_id)
+		OfferRemoved(AccountId, KittyIndex),
 	}
 );
 
@@ -77,6 +198,33 @@ decl_module! {
 
 		fn deposit_event() = default;
 
+		/// The number of blocks a generation 0 kitty must wait between breedings.
+		const BreedCooldown: T::BlockNumber = T::BreedCooldown::get();
+
+		/// The share of each sale paid to the treasury.
+		const MarketplaceFee: Perbill = T::MarketplaceFee::get();
+
+		/// The share of each sale paid to the breeder of the kitty.
+		const BreederRoyalty: Perbill = T::BreederRoyalty::get();
+
+		/// The maximum number of auctions that may end in the same block.
+		const MaxAuctionsPerBlock: u32 = T::MaxAuctionsPerBlock::get();
+
+		/// The smallest amount that may be offered for a kitty.
+		const MinimumOffer: BalanceOf<T> = T::MinimumOffer::get();
+
+		/// The maximum number of open offers on a kitty.
+		const MaxOffersPerKitty: u32 = T::MaxOffersPerKitty::get();
+
+		// Auctions are settled in `on_finalize`, so their weight is accounted for up front
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			Self::settle_auctions_weight(Self::auction_ends(now).len() as u32)
+		}
+
+		fn on_finalize(now: T::BlockNumber) {
+			Self::settle_auctions(now);
+		}
+
 		/// Create a new kitty
 		#[weight = 0]
 		pub fn create(origin) {
@@ -88,7 +236,7 @@ decl_module! {
 
 			// Create and store kitty
 			let kitty = Kitty(dna);
-			Self::insert_kitty(&sender, kitty_id, kitty);
+			Self::insert_kitty(&sender, kitty_id, kitty, None);
 
 			Self::deposit_event(RawEvent::Created(sender, kitty_id));
 		}
@@ -110,6 +258,8 @@ decl_module! {
 
 			ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id))), Error::<T>::RequireOwner);
 
+			ensure!(!<Auctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
 			Self::do_transfer(&sender, &to, kitty_id);
 
 			Self::deposit_event(RawEvent::Transferred(sender, to, kitty_id));
@@ -123,6 +273,8 @@ decl_module! {
 
 			ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id))), Error::<T>::RequireOwner);
 
+			ensure!(!<Auctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
 			<KittyPrices<T>>::mutate_exists(kitty_id, |price| *price = new_price);
 
 			Self::deposit_event(RawEvent::Ask(sender, kitty_id, new_price));
@@ -135,17 +287,251 @@ decl_module! {
 
 			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
 
+			ensure!(!<Auctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
 			let kitty_price = Self::kitty_price(kitty_id).ok_or(Error::<T>::NotForSale)?;
 
 			ensure!(price >= kitty_price, Error::<T>::PriceTooLow);
 
-			T::Currency::transfer(&sender, &owner, kitty_price, ExistenceRequirement::KeepAlive)?;
+			let proceeds = T::Currency::withdraw(&sender, kitty_price, WithdrawReason::Transfer.into(), ExistenceRequirement::KeepAlive)?;
 
 			<KittyPrices<T>>::remove(kitty_id);
 
 			Self::do_transfer(&owner, &sender, kitty_id);
 
-			Self::deposit_event(RawEvent::Sold(owner, sender, kitty_id, kitty_price));
+			let (fee, royalty, seller_proceeds) = Self::pay_proceeds(&owner, kitty_id, proceeds);
+
+			Self::deposit_event(RawEvent::Sold(owner, sender, kitty_id, kitty_price, fee, royalty, seller_proceeds));
+		}
+
+		/// Set a fee for other owners to breed with a kitty
+		/// None to delist the kitty
+		#[weight = 0]
+		pub fn set_sire_price(origin, kitty_id: T::KittyIndex, new_price: Option<BalanceOf<T>>) {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id))), Error::<T>::RequireOwner);
+
+			<SirePrices<T>>::mutate_exists(kitty_id, |price| *price = new_price);
+
+			Self::deposit_event(RawEvent::SireAsk(sender, kitty_id, new_price));
+		}
+
+		/// Breed a kitty with a sire listed by another owner, paying the sire's fee as long as it
+		/// does not exceed `max_fee`. The new kitty goes to the sender
+		#[weight = 0]
+		pub fn breed_with(origin, kitty_id: T::KittyIndex, sire_id: T::KittyIndex, max_fee: BalanceOf<T>) {
+			let sender = ensure_signed(origin)?;
+
+			let (new_kitty_id, kitty, sire) = Self::check_breed(kitty_id, sire_id)?;
+
+			ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id))), Error::<T>::RequireOwner);
+
+			let sire_owner = Self::kitty_owner(sire_id).ok_or(Error::<T>::InvalidKittyId)?;
+
+			let fee = Self::sire_price(sire_id).ok_or(Error::<T>::NotForSiring)?;
+
+			ensure!(fee <= max_fee, Error::<T>::SirePriceTooHigh);
+
+			T::Currency::transfer(&sender, &sire_owner, fee, ExistenceRequirement::KeepAlive)?;
+
+			Self::insert_child(&sender, new_kitty_id, (kitty_id, kitty), (sire_id, sire));
+
+			Self::deposit_event(RawEvent::Created(sender.clone(), new_kitty_id));
+			Self::deposit_event(RawEvent::Sired(sender, sire_owner, sire_id, new_kitty_id, fee));
+		}
+
+		/// Put a kitty up for auction for `duration` blocks, delisting it from sale
+		/// `end_price` is only used by Dutch auctions and must not exceed `start_price`
+		#[weight = 0]
+		pub fn create_auction(
+			origin,
+			kitty_id: T::KittyIndex,
+			kind: AuctionKind,
+			start_price: BalanceOf<T>,
+			end_price: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id))), Error::<T>::RequireOwner);
+
+			ensure!(!<Auctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
+			ensure!(!duration.is_zero(), Error::<T>::InvalidAuction);
+			ensure!(kind == AuctionKind::English || start_price >= end_price, Error::<T>::InvalidAuction);
+
+			let now = <frame_system::Module<T>>::block_number();
+			let end_block = now.saturating_add(duration);
+
+			ensure!(
+				T::MaxAuctionsPerBlock::get() > Self::auction_ends(end_block).len() as u32,
+				Error::<T>::TooManyAuctions
+			);
+
+			<KittyPrices<T>>::remove(kitty_id);
+			<Auctions<T>>::insert(kitty_id, Auction {
+				seller: sender.clone(),
+				kind,
+				start_price,
+				end_price,
+				start_block: now,
+				end_block,
+				highest_bid: None,
+			});
+			<AuctionEnds<T>>::mutate(end_block, |kitties| kitties.push(kitty_id));
+
+			Self::deposit_event(RawEvent::AuctionCreated(sender, kitty_id, end_block));
+		}
+
+		/// Bid on a kitty in auction
+		/// An English bid is reserved until it is outbid or the auction is settled
+		/// A Dutch bid of at least the current price buys the kitty at that price right away
+		#[weight = 0]
+		pub fn bid(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>) {
+			let sender = ensure_signed(origin)?;
+
+			let mut auction = Self::auction(kitty_id).ok_or(Error::<T>::NotInAuction)?;
+
+			ensure!(auction.seller != sender, Error::<T>::BidOnOwnAuction);
+
+			let now = <frame_system::Module<T>>::block_number();
+			ensure!(now <= auction.end_block, Error::<T>::AuctionEnded);
+
+			match auction.kind {
+				AuctionKind::English => {
+					ensure!(amount >= auction.start_price, Error::<T>::BidTooLow);
+					if let Some((_, highest)) = &auction.highest_bid {
+						ensure!(amount > *highest, Error::<T>::BidTooLow);
+					}
+
+					T::Currency::reserve(&sender, amount)?;
+					if let Some((bidder, highest)) = auction.highest_bid.take() {
+						T::Currency::unreserve(&bidder, highest);
+					}
+
+					auction.highest_bid = Some((sender.clone(), amount));
+					<Auctions<T>>::insert(kitty_id, auction);
+
+					Self::deposit_event(RawEvent::Bid(sender, kitty_id, amount));
+				}
+				AuctionKind::Dutch => {
+					let price = Self::dutch_price(&auction, now);
+					ensure!(amount >= price, Error::<T>::BidTooLow);
+
+					let proceeds = T::Currency::withdraw(&sender, price, WithdrawReason::Transfer.into(), ExistenceRequirement::KeepAlive)?;
+
+					Self::remove_auction(kitty_id, auction.end_block);
+					Self::do_transfer(&auction.seller, &sender, kitty_id);
+
+					let (fee, royalty, seller_proceeds) = Self::pay_proceeds(&auction.seller, kitty_id, proceeds);
+
+					Self::deposit_event(RawEvent::Sold(auction.seller, sender, kitty_id, price, fee, royalty, seller_proceeds));
+				}
+			}
+		}
+
+		/// Offer to buy a kitty, whether or not it is for sale, reserving `amount` until the
+		/// offer is accepted or withdrawn. A new offer replaces the bidder's previous one
+		#[weight = 0]
+		pub fn make_offer(origin, kitty_id: T::KittyIndex, amount: BalanceOf<T>, expiry: T::BlockNumber) {
+			let sender = ensure_signed(origin)?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+
+			ensure!(owner != sender, Error::<T>::OfferOnOwnKitty);
+
+			ensure!(expiry >= <frame_system::Module<T>>::block_number(), Error::<T>::OfferExpired);
+
+			ensure!(amount >= T::MinimumOffer::get(), Error::<T>::OfferTooLow);
+
+			match Self::offer(kitty_id, &sender) {
+				// Only the difference from the previous offer is reserved or released
+				Some(previous) => {
+					if amount > previous.amount {
+						T::Currency::reserve(&sender, amount - previous.amount)?;
+					} else {
+						T::Currency::unreserve(&sender, previous.amount - amount);
+					}
+				}
+				None => {
+					ensure!(T::MaxOffersPerKitty::get() > Self::offer_count(kitty_id), Error::<T>::TooManyOffers);
+
+					T::Currency::reserve(&sender, amount)?;
+					<OfferCounts<T>>::mutate(kitty_id, |count| *count += 1);
+				}
+			}
+
+			<Offers<T>>::insert(kitty_id, &sender, Offer { amount, expiry });
+
+			Self::deposit_event(RawEvent::OfferMade(sender, kitty_id, amount, expiry));
+		}
+
+		/// Withdraw an offer, releasing its reserved funds
+		#[weight = 0]
+		pub fn withdraw_offer(origin, kitty_id: T::KittyIndex) {
+			let sender = ensure_signed(origin)?;
+
+			let offer = <Offers<T>>::take(kitty_id, &sender).ok_or(Error::<T>::OfferNotFound)?;
+
+			Self::release_offer(kitty_id, &sender, offer.amount);
+
+			Self::deposit_event(RawEvent::OfferWithdrawn(sender, kitty_id));
+		}
+
+		/// Remove an expired offer, releasing its reserved funds and freeing its slot
+		/// Anyone may remove an expired offer
+		#[weight = 0]
+		pub fn remove_expired_offer(origin, kitty_id: T::KittyIndex, bidder: T::AccountId) {
+			ensure_signed(origin)?;
+
+			let offer = Self::offer(kitty_id, &bidder).ok_or(Error::<T>::OfferNotFound)?;
+
+			ensure!(offer.expiry < <frame_system::Module<T>>::block_number(), Error::<T>::OfferNotExpired);
+
+			<Offers<T>>::remove(kitty_id, &bidder);
+			Self::release_offer(kitty_id, &bidder, offer.amount);
+
+			Self::deposit_event(RawEvent::OfferRemoved(bidder, kitty_id));
+		}
+
+		/// Accept an offer on a kitty, selling it to `bidder`
+		/// Every other offer on the kitty is released
+		#[weight = T::DbWeight::get().reads_writes(
+			8 + 2 * T::MaxOffersPerKitty::get() as Weight,
+			10 + 2 * T::MaxOffersPerKitty::get() as Weight,
+		)]
+		pub fn accept_offer(origin, kitty_id: T::KittyIndex, bidder: T::AccountId) {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id))), Error::<T>::RequireOwner);
+
+			ensure!(!<Auctions<T>>::contains_key(kitty_id), Error::<T>::KittyInAuction);
+
+			// An offer made before the bidder came to own the kitty
+			ensure!(bidder != sender, Error::<T>::OfferOnOwnKitty);
+
+			let offer = Self::offer(kitty_id, &bidder).ok_or(Error::<T>::OfferNotFound)?;
+
+			ensure!(offer.expiry >= <frame_system::Module<T>>::block_number(), Error::<T>::OfferExpired);
+
+			// The offer was reserved when it was made, so it can always be paid in full
+			let (proceeds, _) = T::Currency::slash_reserved(&bidder, offer.amount);
+
+			for (other, other_offer) in <Offers<T>>::drain_prefix(kitty_id) {
+				if other != bidder {
+					T::Currency::unreserve(&other, other_offer.amount);
+				}
+			}
+			<OfferCounts<T>>::remove(kitty_id);
+
+			<KittyPrices<T>>::remove(kitty_id);
+
+			Self::do_transfer(&sender, &bidder, kitty_id);
+
+			let (fee, royalty, seller_proceeds) = Self::pay_proceeds(&sender, kitty_id, proceeds);
+
+			Self::deposit_event(RawEvent::Sold(sender, bidder, kitty_id, offer.amount, fee, royalty, seller_proceeds));
 		}
 	}
 }
@@ -177,29 +563,75 @@ impl<T: Trait> Module<T> {
 		<KittyOwners<T>>::insert(kitty_id, owner);
 	}
 
-	fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: Kitty) {
+	fn insert_kitty(owner: &T::AccountId, kitty_id: T::KittyIndex, kitty: Kitty, parents: Option<(T::KittyIndex, T::KittyIndex)>) {
+		let generation = parents.map_or(0, |(parent_1, parent_2)| {
+			Self::generation_of(parent_1).max(Self::generation_of(parent_2)).saturating_add(1)
+		});
+		let now = <frame_system::Module<T>>::block_number();
+
 		// Create and store kitty
 		Kitties::<T>::insert(kitty_id, kitty);
 		KittiesCount::<T>::put(kitty_id + 1.into());
+		KittyGenealogies::<T>::insert(kitty_id, Genealogy { parents, generation, birth_block: now });
+		KittyBreeders::<T>::insert(kitty_id, owner);
+
+		// A newborn kitty has to grow up before it can breed
+		if parents.is_some() {
+			Self::start_cooldown(kitty_id, now);
+		}
 
 		Self::insert_owned_kitty(owner, kitty_id);
 	}
 
+	fn generation_of(kitty_id: T::KittyIndex) -> u32 {
+		Self::kitty_genealogy(kitty_id).map_or(0, |genealogy| genealogy.generation)
+	}
+
+	fn start_cooldown(kitty_id: T::KittyIndex, now: T::BlockNumber) {
+		let cooldown = T::BreedCooldown::get()
+			.saturating_mul(Self::generation_of(kitty_id).saturating_add(1).into());
+		<BreedingReadyAt<T>>::insert(kitty_id, now.saturating_add(cooldown));
+	}
+
 	fn do_breed(sender: &T::AccountId, kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> sp_std::result::Result<T::KittyIndex, DispatchError> {
-		let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-		let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+		let (kitty_id, kitty1, kitty2) = Self::check_breed(kitty_id_1, kitty_id_2)?;
 
 		ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id_1))), Error::<T>::RequireOwner);
 		ensure!(<OwnedKitties<T>>::contains_key((&sender, Some(kitty_id_2))), Error::<T>::RequireOwner);
+
+		Self::insert_child(sender, kitty_id, (kitty_id_1, kitty1), (kitty_id_2, kitty2));
+
+		Ok(kitty_id)
+	}
+
+	/// Checks that two kitties can breed now, returning the id of their child and the parents.
+	fn check_breed(kitty_id_1: T::KittyIndex, kitty_id_2: T::KittyIndex) -> sp_std::result::Result<(T::KittyIndex, Kitty, Kitty), DispatchError> {
+		let kitty1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+		let kitty2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
+
 		ensure!(kitty_id_1 != kitty_id_2, Error::<T>::RequireDifferentParent);
 
+		let now = <frame_system::Module<T>>::block_number();
+		ensure!(Self::breeding_ready_at(kitty_id_1) <= now, Error::<T>::BreedingCooldown);
+		ensure!(Self::breeding_ready_at(kitty_id_2) <= now, Error::<T>::BreedingCooldown);
+
 		let kitty_id = Self::next_kitty_id()?;
 
+		Ok((kitty_id, kitty1, kitty2))
+	}
+
+	/// Breeds a child of two kitties that passed `check_breed`.
+	fn insert_child(
+		owner: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		(kitty_id_1, kitty1): (T::KittyIndex, Kitty),
+		(kitty_id_2, kitty2): (T::KittyIndex, Kitty),
+	) {
 		let kitty1_dna = kitty1.0;
 		let kitty2_dna = kitty2.0;
 
 		// Generate a random 128bit value
-		let selector = Self::random_value(&sender);
+		let selector = Self::random_value(owner);
 		let mut new_dna = [0u8; 16];
 
 		// Combine parents and selector to create new kitty
@@ -207,12 +639,139 @@ impl<T: Trait> Module<T> {
 			new_dna[i] = combine_dna(kitty1_dna[i], kitty2_dna[i], selector[i]);
 		}
 
-		Self::insert_kitty(sender, kitty_id, Kitty(new_dna));
+		Self::insert_kitty(owner, kitty_id, Kitty(new_dna), Some((kitty_id_1, kitty_id_2)));
 
-		Ok(kitty_id)
+		let now = <frame_system::Module<T>>::block_number();
+		Self::start_cooldown(kitty_id_1, now);
+		Self::start_cooldown(kitty_id_2, now);
+	}
+
+	/// The price of a Dutch auction at block `now`, falling linearly over its duration.
+	fn dutch_price(auction: &AuctionOf<T>, now: T::BlockNumber) -> BalanceOf<T> {
+		let elapsed = now.saturating_sub(auction.start_block);
+		let duration = auction.end_block.saturating_sub(auction.start_block);
+		let drop = auction.start_price.saturating_sub(auction.end_price);
+
+		auction.start_price.saturating_sub(Perbill::from_rational_approximation(elapsed, duration) * drop)
+	}
+
+	/// Splits the proceeds of selling `kitty_id` between the treasury, the kitty's breeder and the
+	/// seller, returning the fee, royalty and seller's share paid. The seller gets whatever the
+	/// others cannot take, and the treasury gets the seller's share if it is too little to
+	/// recreate a reaped seller's account.
+	fn pay_proceeds(
+		seller: &T::AccountId,
+		kitty_id: T::KittyIndex,
+		proceeds: NegativeImbalanceOf<T>,
+	) -> (BalanceOf<T>, BalanceOf<T>, BalanceOf<T>) {
+		let price = proceeds.peek();
+		let fee = T::MarketplaceFee::get() * price;
+		// Never pay out more than the price, however the shares are configured
+		let royalty = (T::BreederRoyalty::get() * price).min(price.saturating_sub(fee));
+
+		let (fee_part, rest) = proceeds.split(fee);
+		let (royalty_part, mut rest) = rest.split(royalty);
+
+		let mut fee = match Self::pay(&T::Treasury::get(), fee_part) {
+			Ok(paid) => paid,
+			Err(unpaid) => {
+				rest.subsume(unpaid);
+				Zero::zero()
+			}
+		};
+
+		let royalty = match Self::kitty_breeder(kitty_id) {
+			Some(breeder) => match Self::pay(&breeder, royalty_part) {
+				Ok(paid) => paid,
+				Err(unpaid) => {
+					rest.subsume(unpaid);
+					Zero::zero()
+				}
+			},
+			None => {
+				rest.subsume(royalty_part);
+				Zero::zero()
+			}
+		};
+
+		let seller_proceeds = match Self::pay(seller, rest) {
+			Ok(paid) => paid,
+			Err(unpaid) => {
+				// Whatever the treasury cannot take either is burned
+				if let Ok(paid) = Self::pay(&T::Treasury::get(), unpaid) {
+					fee = fee.saturating_add(paid);
+				}
+				Zero::zero()
+			}
+		};
+
+		(fee, royalty, seller_proceeds)
+	}
+
+	/// Releases an offer that has been taken out of `Offers`.
+	fn release_offer(kitty_id: T::KittyIndex, bidder: &T::AccountId, amount: BalanceOf<T>) {
+		T::Currency::unreserve(bidder, amount);
+		<OfferCounts<T>>::mutate_exists(kitty_id, |count| {
+			*count = count.map(|c| c.saturating_sub(1)).filter(|c| *c > 0);
+		});
+	}
+
+	/// Deposits `amount` into `who`, or hands it back if it is too little to create the account.
+	fn pay(who: &T::AccountId, amount: NegativeImbalanceOf<T>) -> Result<BalanceOf<T>, NegativeImbalanceOf<T>> {
+		let value = amount.peek();
+		if value >= T::Currency::minimum_balance() || !T::Currency::total_balance(who).is_zero() {
+			T::Currency::resolve_creating(who, amount);
+			Ok(value)
+		} else {
+			Err(amount)
+		}
+	}
+
+	fn remove_auction(kitty_id: T::KittyIndex, end_block: T::BlockNumber) {
+		<Auctions<T>>::remove(kitty_id);
+		<AuctionEnds<T>>::mutate_exists(end_block, |kitties| {
+			if let Some(list) = kitties {
+				list.retain(|k| *k != kitty_id);
+				if list.is_empty() {
+					*kitties = None;
+				}
+			}
+		});
+	}
+
+	/// The weight of settling `count` auctions, paying out and transferring each kitty.
+	fn settle_auctions_weight(count: u32) -> Weight {
+		let count = count as Weight;
+		T::DbWeight::get().reads_writes(1 + 11 * count, 1 + 12 * count)
+	}
+
+	/// Settles the auctions ending at `now`, handing each kitty to its highest bidder.
+	fn settle_auctions(now: T::BlockNumber) {
+		for kitty_id in <AuctionEnds<T>>::take(now) {
+			let auction = match <Auctions<T>>::take(kitty_id) {
+				Some(auction) => auction,
+				None => continue,
+			};
+
+			match auction.highest_bid {
+				Some((bidder, amount)) => {
+					// The bid was reserved when it was placed, so it can always be paid in full
+					let (proceeds, _) = T::Currency::slash_reserved(&bidder, amount);
+					Self::do_transfer(&auction.seller, &bidder, kitty_id);
+
+					let (fee, royalty, seller_proceeds) = Self::pay_proceeds(&auction.seller, kitty_id, proceeds);
+
+					Self::deposit_event(RawEvent::Sold(auction.seller, bidder, kitty_id, amount, fee, royalty, seller_proceeds));
+				}
+				None => Self::deposit_event(RawEvent::AuctionExpired(auction.seller, kitty_id)),
+			}
+		}
 	}
 
 	fn do_transfer(from: &T::AccountId, to: &T::AccountId, kitty_id: T::KittyIndex)  {
+		// A sire listing is the previous owner's offer, not the new owner's
+		<SirePrices<T>>::remove(kitty_id);
+
 		<OwnedKittiesList<T>>::remove(&from, kitty_id);
 		Self::insert_owned_kitty(&to, kitty_id);
 	}
@@ -223,8 +782,9 @@ impl<T: Trait> Module<T> {
 mod tests {
 	use super::*;
 
+	use std::cell::RefCell;
 	use sp_core::H256;
-	use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+	use frame_support::{assert_ok, assert_noop, impl_outer_origin, parameter_types, weights::Weight, traits::OnFinalize};
 	use sp_runtime::{
 		traits::{BlakeTwo256, IdentityLookup}, testing::Header, Perbill,
 	};
@@ -266,19 +826,61 @@ mod tests {
 		type AvailableBlockRatio = AvailableBlockRatio;
 		type Version = ();
 		type ModuleToIndex = ();
-		type AccountData = ();
+		type AccountData = pallet_balances::AccountData<u64>;
 		type OnNewAccount = ();
 		type OnKilledAccount = ();
 	}
+	thread_local! {
+		static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+	}
+	pub struct ExistentialDeposit;
+	impl Get<u64> for ExistentialDeposit {
+		fn get() -> u64 {
+			EXISTENTIAL_DEPOSIT.with(|v| *v.borrow())
+		}
+	}
+	impl pallet_balances::Trait for Test {
+		type Balance = u64;
+		type Event = ();
+		type DustRemoval = ();
+		type ExistentialDeposit = ExistentialDeposit;
+		type AccountStore = System;
+	}
+	parameter_types! {
+		pub const BreedCooldown: u64 = 5;
+		pub const MarketplaceFee: Perbill = Perbill::from_percent(10);
+		pub const BreederRoyalty: Perbill = Perbill::from_percent(5);
+		pub const Treasury: u64 = 100;
+		pub const MaxAuctionsPerBlock: u32 = 2;
+		pub const MinimumOffer: u64 = 5;
+		pub const MaxOffersPerKitty: u32 = 2;
+	}
 	impl Trait for Test {
+		type Event = ();
 		type KittyIndex = u32;
+		type Currency = Balances;
+		type Randomness = pallet_randomness_collective_flip::Module<Test>;
+		type BreedCooldown = BreedCooldown;
+		type MarketplaceFee = MarketplaceFee;
+		type BreederRoyalty = BreederRoyalty;
+		type Treasury = Treasury;
+		type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+		type MinimumOffer = MinimumOffer;
+		type MaxOffersPerKitty = MaxOffersPerKitty;
 	}
 	type OwnedKittiesTest = OwnedKitties<Test>;
+	type System = system::Module<Test>;
+	type Balances = pallet_balances::Module<Test>;
+	type KittiesModule = Module<Test>;
 
 	// This function basically just builds a genesis storage key/value store according to
 	// our desired mockup.
 	fn new_test_ext() -> sp_io::TestExternalities {
-		system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+		let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		pallet_balances::GenesisConfig::<Test> {
+			balances: vec![(1, 1000), (2, 1000), (3, 1000)],
+		}.assimilate_storage(&mut t).unwrap();
+		t.into()
 	}
 
 	#[test]
@@ -393,4 +995,392 @@ mod tests {
 			assert_eq!(OwnedKittiesTest::get(&(0, Some(2))), None);
 		});
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn created_kitty_is_generation_zero() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(3);
+
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_eq!(KittiesModule::kitty_genealogy(0), Some(Genealogy {
+				parents: None,
+				generation: 0,
+				birth_block: 3,
+			}));
+			assert_eq!(KittiesModule::breeding_ready_at(0), 0);
+		});
+	}
+
+	#[test]
+	fn breed_records_parents_and_cooldown() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+
+			assert_eq!(KittiesModule::kitty_genealogy(2), Some(Genealogy {
+				parents: Some((0, 1)),
+				generation: 1,
+				birth_block: 1,
+			}));
+			assert_eq!(KittiesModule::breeding_ready_at(0), 6);
+			assert_eq!(KittiesModule::breeding_ready_at(1), 6);
+			// The cooldown grows with the generation
+			assert_eq!(KittiesModule::breeding_ready_at(2), 11);
+
+			assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 1), Error::<Test>::BreedingCooldown);
+
+			System::set_block_number(6);
+			assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1));
+			assert_noop!(KittiesModule::breed(Origin::signed(1), 2, 3), Error::<Test>::BreedingCooldown);
+
+			// Kitty 3 is generation 1 as well, born at block 6
+			System::set_block_number(16);
+			assert_ok!(KittiesModule::breed(Origin::signed(1), 2, 3));
+			assert_eq!(KittiesModule::kitty_genealogy(4).map(|g| g.generation), Some(2));
+		});
+	}
+
+	#[test]
+	fn breed_with_pays_sire_owner() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::create(Origin::signed(2)));
+
+			assert_noop!(KittiesModule::breed(Origin::signed(1), 0, 1), Error::<Test>::RequireOwner);
+			assert_noop!(KittiesModule::breed_with(Origin::signed(1), 0, 1, 10), Error::<Test>::NotForSiring);
+			assert_noop!(KittiesModule::set_sire_price(Origin::signed(1), 1, Some(10)), Error::<Test>::RequireOwner);
+
+			assert_ok!(KittiesModule::set_sire_price(Origin::signed(2), 1, Some(10)));
+			assert_eq!(KittiesModule::sire_price(1), Some(10));
+
+			assert_noop!(KittiesModule::breed_with(Origin::signed(2), 0, 1, 10), Error::<Test>::RequireOwner);
+			assert_ok!(KittiesModule::breed_with(Origin::signed(1), 0, 1, 10));
+
+			assert_eq!(KittiesModule::kitty_owner(2), Some(1));
+			assert_eq!(KittiesModule::kitty_genealogy(2).map(|g| g.parents), Some(Some((0, 1))));
+			assert_eq!(Balances::free_balance(1), 990);
+			assert_eq!(Balances::free_balance(2), 1010);
+		});
+	}
+
+	#[test]
+	fn breed_with_rejects_raised_sire_price() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::create(Origin::signed(2)));
+			assert_ok!(KittiesModule::set_sire_price(Origin::signed(2), 1, Some(10)));
+
+			// The sire owner raises the fee before the breeding goes through
+			assert_ok!(KittiesModule::set_sire_price(Origin::signed(2), 1, Some(50)));
+
+			assert_noop!(KittiesModule::breed_with(Origin::signed(1), 0, 1, 10), Error::<Test>::SirePriceTooHigh);
+			assert_eq!(Balances::free_balance(1), 1000);
+		});
+	}
+
+	#[test]
+	fn breed_with_fails_without_funds() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(KittiesModule::create(Origin::signed(4)));
+			assert_ok!(KittiesModule::create(Origin::signed(2)));
+			assert_ok!(KittiesModule::set_sire_price(Origin::signed(2), 1, Some(10)));
+
+			assert_noop!(
+				KittiesModule::breed_with(Origin::signed(4), 0, 1, 10),
+				pallet_balances::Error::<Test>::InsufficientBalance
+			);
+			assert_eq!(KittiesModule::kitty_genealogy(2), None);
+		});
+	}
+
+	#[test]
+	fn english_auction_goes_to_highest_bidder() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_noop!(
+				KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 0, 0),
+				Error::<Test>::InvalidAuction
+			);
+			assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 0, 5));
+
+			assert_noop!(KittiesModule::transfer(Origin::signed(1), 2, 0), Error::<Test>::KittyInAuction);
+			assert_noop!(KittiesModule::ask(Origin::signed(1), 0, Some(5)), Error::<Test>::KittyInAuction);
+			assert_noop!(KittiesModule::bid(Origin::signed(1), 0, 20), Error::<Test>::BidOnOwnAuction);
+			assert_noop!(KittiesModule::bid(Origin::signed(2), 0, 9), Error::<Test>::BidTooLow);
+
+			assert_ok!(KittiesModule::bid(Origin::signed(2), 0, 10));
+			assert_eq!(Balances::reserved_balance(2), 10);
+
+			assert_noop!(KittiesModule::bid(Origin::signed(3), 0, 10), Error::<Test>::BidTooLow);
+			assert_ok!(KittiesModule::bid(Origin::signed(3), 0, 20));
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(Balances::reserved_balance(3), 20);
+
+			KittiesModule::on_finalize(5);
+			assert!(KittiesModule::auction(0).is_some());
+
+			System::set_block_number(7);
+			assert_noop!(KittiesModule::bid(Origin::signed(2), 0, 30), Error::<Test>::AuctionEnded);
+
+			KittiesModule::on_finalize(6);
+			assert_eq!(KittiesModule::auction(0), None);
+			assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+			assert_eq!(Balances::reserved_balance(3), 0);
+			assert_eq!(Balances::free_balance(3), 980);
+			// The seller bred the kitty, so gets the royalty as well as the price less the fee
+			assert_eq!(Balances::free_balance(1), 1018);
+			assert_eq!(Balances::free_balance(100), 2);
+		});
+	}
+
+	#[test]
+	fn dutch_auction_sells_at_falling_price() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_noop!(
+				KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::Dutch, 10, 20, 5),
+				Error::<Test>::InvalidAuction
+			);
+			assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::Dutch, 100, 60, 10));
+
+			// Halfway through, the price is halfway between the start and end prices
+			System::set_block_number(6);
+			assert_noop!(KittiesModule::bid(Origin::signed(2), 0, 79), Error::<Test>::BidTooLow);
+			assert_ok!(KittiesModule::bid(Origin::signed(2), 0, 90));
+
+			assert_eq!(KittiesModule::auction(0), None);
+			assert_eq!(KittiesModule::auction_ends(11), Vec::<u32>::new());
+			assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+			assert_eq!(Balances::free_balance(2), 920);
+			assert_eq!(Balances::free_balance(1), 1072);
+			assert_eq!(Balances::free_balance(100), 8);
+		});
+	}
+
+	#[test]
+	fn auction_without_bids_expires() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 0, 5));
+
+			KittiesModule::on_finalize(6);
+
+			assert_eq!(KittiesModule::auction(0), None);
+			assert_eq!(KittiesModule::kitty_owner(0), Some(1));
+			assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+		});
+	}
+
+	#[test]
+	fn create_auction_failed_when_too_many_auctions_end_in_block() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_ok!(KittiesModule::create_auction(Origin::signed(1), 0, AuctionKind::English, 10, 0, 5));
+			assert_ok!(KittiesModule::create_auction(Origin::signed(1), 1, AuctionKind::English, 10, 0, 5));
+
+			assert_noop!(
+				KittiesModule::create_auction(Origin::signed(1), 2, AuctionKind::English, 10, 0, 5),
+				Error::<Test>::TooManyAuctions
+			);
+			assert_ok!(KittiesModule::create_auction(Origin::signed(1), 2, AuctionKind::English, 10, 0, 6));
+		});
+	}
+
+	#[test]
+	fn buy_splits_price_between_seller_treasury_and_breeder() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+			assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(100)));
+
+			assert_ok!(KittiesModule::buy(Origin::signed(3), 0, 100));
+
+			assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+			assert_eq!(Balances::free_balance(3), 900);
+			assert_eq!(Balances::free_balance(100), 10);
+			assert_eq!(Balances::free_balance(1), 1005);
+			assert_eq!(Balances::free_balance(2), 1085);
+		});
+	}
+
+	#[test]
+	fn buy_pays_dust_of_reaped_seller_to_treasury() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::transfer(Origin::signed(1), 4, 0));
+			assert_ok!(KittiesModule::ask(Origin::signed(4), 0, Some(10)));
+
+			// The seller's share is too little to recreate its account
+			EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = 20);
+			let _ = Balances::deposit_creating(&100, 50);
+
+			let issuance = Balances::total_issuance();
+			assert_ok!(KittiesModule::buy(Origin::signed(3), 0, 10));
+
+			assert_eq!(KittiesModule::kitty_owner(0), Some(3));
+			assert_eq!(Balances::free_balance(4), 0);
+			assert_eq!((Balances::free_balance(100) - 50) + (Balances::free_balance(1) - 1000), 10);
+			assert_eq!(Balances::total_issuance(), issuance);
+		});
+	}
+
+	#[test]
+	fn buy_never_loses_rounding_dust() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+			assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+			assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(7)));
+
+			let issuance = Balances::total_issuance();
+			assert_ok!(KittiesModule::buy(Origin::signed(3), 0, 7));
+
+			assert_eq!(Balances::free_balance(3), 993);
+			assert_eq!(
+				Balances::free_balance(100) + (Balances::free_balance(1) - 1000) + (Balances::free_balance(2) - 1000),
+				7
+			);
+			assert_eq!(Balances::total_issuance(), issuance);
+		});
+	}
+
+	#[test]
+	fn accept_offer_sells_kitty_and_releases_other_offers() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_noop!(KittiesModule::make_offer(Origin::signed(1), 0, 60, 10), Error::<Test>::OfferOnOwnKitty);
+			assert_noop!(KittiesModule::make_offer(Origin::signed(2), 1, 60, 10), Error::<Test>::InvalidKittyId);
+			assert_noop!(KittiesModule::make_offer(Origin::signed(2), 0, 60, 0), Error::<Test>::OfferExpired);
+
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 60, 10));
+			assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 40, 10));
+			assert_eq!(Balances::reserved_balance(2), 60);
+			assert_eq!(Balances::reserved_balance(3), 40);
+
+			assert_noop!(KittiesModule::accept_offer(Origin::signed(2), 0, 3), Error::<Test>::RequireOwner);
+			assert_noop!(KittiesModule::accept_offer(Origin::signed(1), 0, 4), Error::<Test>::OfferNotFound);
+
+			assert_ok!(KittiesModule::accept_offer(Origin::signed(1), 0, 2));
+
+			assert_eq!(KittiesModule::kitty_owner(0), Some(2));
+			assert_eq!(KittiesModule::offer(0, 2), None);
+			assert_eq!(KittiesModule::offer(0, 3), None);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(Balances::free_balance(2), 940);
+			assert_eq!(Balances::reserved_balance(3), 0);
+			assert_eq!(Balances::free_balance(3), 1000);
+			assert_eq!(Balances::free_balance(100), 6);
+			assert_eq!(Balances::free_balance(1), 1054);
+		});
+	}
+
+	#[test]
+	fn offers_can_be_replaced_and_withdrawn() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 60, 10));
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 30, 10));
+			assert_eq!(Balances::reserved_balance(2), 30);
+			assert_eq!(KittiesModule::offer(0, 2), Some(Offer { amount: 30, expiry: 10 }));
+
+			System::set_block_number(11);
+			assert_noop!(KittiesModule::accept_offer(Origin::signed(1), 0, 2), Error::<Test>::OfferExpired);
+
+			assert_ok!(KittiesModule::withdraw_offer(Origin::signed(2), 0));
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(KittiesModule::offer_count(0), 0);
+			assert_noop!(KittiesModule::withdraw_offer(Origin::signed(2), 0), Error::<Test>::OfferNotFound);
+		});
+	}
+
+	#[test]
+	fn raising_an_offer_reserves_only_the_difference() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 600, 10));
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 900, 10));
+
+			assert_eq!(Balances::reserved_balance(2), 900);
+			assert_eq!(Balances::free_balance(2), 100);
+			assert_eq!(KittiesModule::offer_count(0), 1);
+		});
+	}
+
+	#[test]
+	fn offers_are_bounded() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_noop!(KittiesModule::make_offer(Origin::signed(2), 0, 4, 10), Error::<Test>::OfferTooLow);
+
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 5, 10));
+			assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 5, 10));
+			assert_eq!(KittiesModule::offer_count(0), 2);
+
+			assert_noop!(KittiesModule::make_offer(Origin::signed(4), 0, 5, 10), Error::<Test>::TooManyOffers);
+
+			// Replacing an offer does not take up another slot
+			assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 6, 10));
+
+			assert_ok!(KittiesModule::accept_offer(Origin::signed(1), 0, 2));
+			assert_eq!(KittiesModule::offer_count(0), 0);
+		});
+	}
+
+	#[test]
+	fn expired_offers_can_be_removed_by_anyone() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 5, 10));
+			assert_ok!(KittiesModule::make_offer(Origin::signed(3), 0, 5, 20));
+
+			assert_noop!(KittiesModule::remove_expired_offer(Origin::signed(4), 0, 2), Error::<Test>::OfferNotExpired);
+			assert_noop!(KittiesModule::remove_expired_offer(Origin::signed(4), 0, 4), Error::<Test>::OfferNotFound);
+
+			System::set_block_number(11);
+			assert_ok!(KittiesModule::remove_expired_offer(Origin::signed(4), 0, 2));
+
+			assert_eq!(KittiesModule::offer(0, 2), None);
+			assert_eq!(Balances::reserved_balance(2), 0);
+			assert_eq!(KittiesModule::offer_count(0), 1);
+
+			// The freed slot can be taken again
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 5, 20));
+			assert_eq!(KittiesModule::offer_count(0), 2);
+		});
+	}
+
+	#[test]
+	fn accept_offer_rejects_offer_from_owner() {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(KittiesModule::create(Origin::signed(1)));
+
+			assert_ok!(KittiesModule::make_offer(Origin::signed(2), 0, 60, 10));
+			assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+
+			assert_noop!(KittiesModule::accept_offer(Origin::signed(2), 0, 2), Error::<Test>::OfferOnOwnKitty);
+		});
+	}
+}
@@ -11,11 +11,12 @@ include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 use sp_std::prelude::*;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
-	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature,
+	ApplyExtrinsicResult, generic, create_runtime_str, impl_opaque_keys, MultiSignature, ModuleId,
 	transaction_validity::{TransactionValidity, TransactionSource},
 };
 use sp_runtime::traits::{
 	BlakeTwo256, Block as BlockT, IdentityLookup, Verify, IdentifyAccount, NumberFor, Saturating,
+	AccountIdConversion,
 };
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -33,7 +34,7 @@ pub use balances::Call as BalancesCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, StorageValue,
-	traits::{KeyOwnerProofSystem, Randomness},
+	traits::{Get, KeyOwnerProofSystem, Randomness},
 	weights::{
 		Weight, IdentityFee,
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -257,11 +258,36 @@ impl template::Trait for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const BreedCooldown: BlockNumber = MINUTES;
+	pub const MarketplaceFee: Perbill = Perbill::from_percent(2);
+	pub const BreederRoyalty: Perbill = Perbill::from_percent(3);
+	pub const KittiesModuleId: ModuleId = ModuleId(*b"py/kitty");
+	pub const MaxAuctionsPerBlock: u32 = 50;
+	pub const MinimumOffer: Balance = 5_000;
+	pub const MaxOffersPerKitty: u32 = 20;
+}
+
+/// The account collecting kitty marketplace fees.
+pub struct KittiesTreasury;
+impl Get<AccountId> for KittiesTreasury {
+	fn get() -> AccountId {
+		KittiesModuleId::get().into_account()
+	}
+}
+
 impl pallet_kitties::Trait for Runtime {
 	type Event = Event;
 	type KittyIndex = u32;
 	type Currency = Balances;
 	type Randomness = RandomnessCollectiveFlip;
+	type BreedCooldown = BreedCooldown;
+	type MarketplaceFee = MarketplaceFee;
+	type BreederRoyalty = BreederRoyalty;
+	type Treasury = KittiesTreasury;
+	type MaxAuctionsPerBlock = MaxAuctionsPerBlock;
+	type MinimumOffer = MinimumOffer;
+	type MaxOffersPerKitty = MaxOffersPerKitty;
 }
 
 construct_runtime!(